@@ -0,0 +1,64 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use glsl_layout::{float, mat4, vec3, vec4, Uniform};
+
+#[derive(Clone, Copy, Uniform)]
+struct Instance {
+    transform: mat4,
+    color: vec4,
+    position: vec3,
+    scale: float,
+}
+
+const COUNT: usize = 10_000;
+
+/// Convert every value with `std140()` and copy its bytes.
+fn per_element<T: Uniform>(values: &[T], out: &mut Vec<u8>) {
+    for value in values {
+        let std140 = value.std140();
+        let element = glsl_layout::Element::from(std140);
+        let bytes = unsafe {
+            std::slice::from_raw_parts(
+                &element as *const _ as *const u8,
+                std::mem::size_of_val(&element),
+            )
+        };
+        out.extend_from_slice(bytes);
+    }
+}
+
+fn bench<T: Uniform>(c: &mut Criterion, name: &str, value: T) {
+    let values = vec![value; COUNT];
+    let mut out = Vec::with_capacity(COUNT * 128);
+    let mut group = c.benchmark_group(name);
+    group.bench_function("per_element", |b| {
+        b.iter(|| {
+            out.clear();
+            per_element(black_box(&values), &mut out);
+        })
+    });
+    group.bench_function("slice_to_std140", |b| {
+        b.iter(|| {
+            out.clear();
+            T::slice_to_std140(black_box(&values), &mut out);
+        })
+    });
+    group.finish();
+}
+
+fn slice(c: &mut Criterion) {
+    bench(c, "vec4", vec4::from([1.0, 2.0, 3.0, 4.0]));
+    bench(c, "mat4", mat4::from([[1.0, 2.0, 3.0, 4.0]; 4]));
+    bench(
+        c,
+        "instance",
+        Instance {
+            transform: [[1.0, 2.0, 3.0, 4.0]; 4].into(),
+            color: [1.0, 1.0, 1.0, 1.0].into(),
+            position: [1.0, 2.0, 3.0].into(),
+            scale: 1.0,
+        },
+    );
+}
+
+criterion_group!(benches, slice);
+criterion_main!(benches);
@@ -0,0 +1,167 @@
+use glsl_layout_parser::{Member, Module, Scalar, Type};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+
+/// Generate structures with `derive(Uniform)` for structures and blocks declared in GLSL.
+pub fn glsl_block(input: TokenStream) -> syn::Result<TokenStream> {
+    let mut source = String::new();
+    write_source(input, &mut source)?;
+    let module =
+        Module::parse(&source).map_err(|err| syn::Error::new(Span::call_site(), err.message))?;
+
+    let constants = module.constants.iter().map(|(name, value)| {
+        let name = syn::Ident::new(name, Span::call_site());
+        quote!(pub const #name: usize = #value;)
+    });
+
+    let structs = module
+        .structs
+        .iter()
+        .map(|s| structure(&module, &s.name, &s.members, None, "structure"))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let blocks = module
+        .blocks
+        .iter()
+        .map(|b| {
+            let rules = match b.packing() {
+                "std140" => None,
+                "std430" => Some(quote!(#[glsl(layouts(std430))])),
+                "scalar" => Some(quote!(#[glsl(layouts(scalar))])),
+                packing => {
+                    return Err(syn::Error::new(
+                        Span::call_site(),
+                        format!("Layout `{}` of `{}` is not supported", packing, b.name),
+                    ))
+                }
+            };
+            structure(&module, &b.name, &b.members, rules, "block")
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #(#constants)*
+        #(#structs)*
+        #(#blocks)*
+    })
+}
+
+/// Restore GLSL source from tokens.
+/// Directives are put on separate lines, attributes such as doc comments are skipped.
+fn write_source(input: TokenStream, source: &mut String) -> syn::Result<()> {
+    let mut tokens = input.into_iter();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(ref punct) if punct.as_char() == '#' => match tokens.next() {
+                Some(TokenTree::Ident(ref directive)) if directive == "define" => {
+                    let name = tokens.next();
+                    let value = tokens.next();
+                    match (name, value) {
+                        (Some(name), Some(value)) => {
+                            *source += &format!("\n#define {} {}\n", name, value)
+                        }
+                        _ => {
+                            return Err(syn::Error::new(
+                                directive.span(),
+                                "Expected `#define NAME value`",
+                            ))
+                        }
+                    }
+                }
+                Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Bracket => {}
+                Some(token) => {
+                    return Err(syn::Error::new(
+                        token.span(),
+                        "Only `#define` directives are supported",
+                    ))
+                }
+                None => return Err(syn::Error::new(punct.span(), "Unexpected `#`")),
+            },
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::None => ("", ""),
+                };
+                *source += open;
+                write_source(group.stream(), source)?;
+                *source += close;
+                source.push(' ');
+            }
+            token => {
+                *source += &token.to_string();
+                source.push(' ');
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Structure with `derive(Uniform)` and given members.
+fn structure(
+    module: &Module,
+    name: &str,
+    members: &[Member],
+    rules: Option<TokenStream>,
+    kind: &str,
+) -> syn::Result<TokenStream> {
+    let doc = format!("Generated from GLSL {} `{}`.", kind, name);
+    let name = ident(name);
+    let fields = members
+        .iter()
+        .map(|member| {
+            let ty = rust_type(&member.ty, &member.dims).map_err(|message| {
+                syn::Error::new(
+                    Span::call_site(),
+                    format!("`{}` of `{}`: {}", member.name, name, message),
+                )
+            })?;
+            let name = ident(&member.name);
+            Ok(quote!(pub #name: #ty))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let default = if module.derives_default(members) {
+        Some(quote!(Default,))
+    } else {
+        None
+    };
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Copy, Debug, #default glsl_layout::Uniform)]
+        #rules
+        pub struct #name {
+            #(#fields,)*
+        }
+    })
+}
+
+/// Rust type for GLSL type with array dimensions.
+fn rust_type(ty: &Type, dims: &[Option<usize>]) -> Result<TokenStream, String> {
+    if let Some((&len, dims)) = dims.split_first() {
+        let len = len.ok_or_else(|| {
+            "Runtime-sized arrays are not supported, use `UnsizedBlock` instead".to_owned()
+        })?;
+        let elem = rust_type(ty, dims)?;
+        return Ok(quote!([#elem; #len]));
+    }
+
+    Ok(match ty {
+        Type::Scalar(Scalar::Bool) => quote!(glsl_layout::boolean),
+        Type::Struct(name) => {
+            let name = ident(name);
+            quote!(#name)
+        }
+        ty => {
+            let name = ident(&ty.name());
+            quote!(glsl_layout::#name)
+        }
+    })
+}
+
+/// Identifier for GLSL name, raw if it is a Rust keyword.
+fn ident(name: &str) -> syn::Ident {
+    syn::parse_str::<syn::Ident>(name)
+        .unwrap_or_else(|_| syn::Ident::new_raw(name, Span::call_site()))
+}
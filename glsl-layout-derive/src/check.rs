@@ -0,0 +1,248 @@
+use glsl_layout_parser::{Module, Scalar, Type};
+use proc_macro2::{Span, TokenStream};
+use std::path::Path;
+use Member;
+
+/// Check that members match block or structure `block` declared in GLSL file `path`
+/// or files it includes.
+/// Names, number and order of members are checked right away,
+/// types and offsets are checked by constant assertions.
+/// Blocks are checked with their own layout, structures with layouts from `packings`
+/// that GLSL has, or `std140` if there are none.
+pub fn check(
+    name: &syn::Ident,
+    path: &syn::LitStr,
+    block: &str,
+    packings: &[String],
+    members: &[Member],
+) -> syn::Result<TokenStream> {
+    let file = path.value();
+    let full = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => Path::new(&dir).join(&file),
+        None => Path::new(&file).to_owned(),
+    };
+    let mut module = Module::default();
+    let mut read = Vec::new();
+    module
+        .load(&full, &mut Vec::new(), &mut |_, path| {
+            read.push(path.to_string_lossy().into_owned())
+        })
+        .map_err(|err| syn::Error::new(path.span(), format!("`{}`: {}", file, err)))?;
+
+    let (kind, glsl_members, packings) = match module.find_block(block) {
+        Some(b) => ("block", &b.members, vec![b.packing()]),
+        None => match module.find_struct(block) {
+            Some(s) => {
+                let mut packings = packings
+                    .iter()
+                    .map(String::as_str)
+                    .filter(|packing| ["std140", "std430", "scalar"].contains(packing))
+                    .collect::<Vec<_>>();
+                if packings.is_empty() {
+                    packings.push("std140");
+                }
+                ("structure", &s.members, packings)
+            }
+            None => {
+                return Err(syn::Error::new(
+                    path.span(),
+                    format!("No block or structure `{}` in `{}`", block, file),
+                ))
+            }
+        },
+    };
+    let rules = packings
+        .iter()
+        .map(|&packing| {
+            let rules = match packing {
+                "std140" => "Std140",
+                "std430" => "Std430",
+                "scalar" => "Scalar",
+                _ => {
+                    return Err(syn::Error::new(
+                        path.span(),
+                        format!("Layout `{}` of `{}` can't be checked", packing, block),
+                    ))
+                }
+            };
+            Ok((packing, syn::Ident::new(rules, Span::call_site())))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut errors: Option<syn::Error> = None;
+    let mut error = |span: Span, message: String| {
+        let error = syn::Error::new(span, message);
+        match &mut errors {
+            Some(errors) => errors.combine(error),
+            None => errors = Some(error),
+        }
+    };
+    for (member, glsl) in members.iter().zip(glsl_members) {
+        if member.ident.to_string().trim_start_matches("r#") != glsl.name {
+            error(
+                member.ident.span(),
+                format!(
+                    "Expected member `{}` of {} `{}` declared in `{}`",
+                    glsl.name, kind, block, file
+                ),
+            );
+        }
+    }
+    for member in members.iter().skip(glsl_members.len()) {
+        error(
+            member.ident.span(),
+            format!(
+                "Member `{}` is not declared in {} `{}` in `{}`",
+                member.ident.to_string().trim_start_matches("r#"),
+                kind,
+                block,
+                file
+            ),
+        );
+    }
+    for glsl in glsl_members.iter().skip(members.len()) {
+        error(
+            name.span(),
+            format!(
+                "Member `{}` of {} `{}` declared in `{}` is missing",
+                glsl.name, kind, block, file
+            ),
+        );
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let glsl_infos = glsl_members
+        .iter()
+        .map(|member| {
+            let ty = type_info(&module, &member.ty, &member.dims)
+                .map_err(|message| syn::Error::new(path.span(), message))?;
+            let name = &member.name;
+            Ok(quote!(glsl_layout::reflect::MemberInfo::new(#name, &#ty)))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let asserts = members
+        .iter()
+        .zip(glsl_members)
+        .enumerate()
+        .map(|(index, (member, glsl))| {
+            let ty = &member.ty;
+            let declared = declaration(&glsl.ty, &glsl.dims, &glsl.name);
+            let type_message = format!(
+                "Type of `{}` doesn't match `{}` declared in `{}`",
+                glsl.name, declared, file
+            );
+            let offsets = rules.iter().map(|(packing, rules)| {
+                let offset_message = format!(
+                    "Offset of `{}` doesn't match {} offset of `{}` declared in `{}`",
+                    glsl.name, packing, declared, file
+                );
+                quote_spanned! {member.ident.span()=>
+                    assert!(
+                        glsl_layout::layout::member_offset::<glsl_layout::layout::#rules>(
+                            &<#name as glsl_layout::Uniform>::TYPE_INFO,
+                            #index,
+                        ) == glsl_layout::layout::member_offset::<glsl_layout::layout::#rules>(
+                            &glsl_layout::reflect::TypeInfo::Struct(&GLSL),
+                            #index,
+                        ),
+                        #offset_message,
+                    );
+                }
+            });
+            quote_spanned! {member.ident.span()=>
+                assert!(
+                    glsl_layout::reflect::TypeInfo::same(
+                        &<#ty as glsl_layout::Uniform>::TYPE_INFO,
+                        GLSL.members[#index].ty,
+                    ),
+                    #type_message,
+                );
+                #(#offsets)*
+            }
+        });
+
+    Ok(quote! {
+        // Rebuild when any of the files change.
+        #(const _: &[u8] = include_bytes!(#read);)*
+
+        const _: () = {
+            const GLSL: glsl_layout::reflect::StructInfo =
+                glsl_layout::reflect::StructInfo::new(#block, &[#(#glsl_infos,)*]);
+            #(#asserts)*
+        };
+    })
+}
+
+/// Expression of `TypeInfo` for GLSL type.
+fn type_info(module: &Module, ty: &Type, dims: &[Option<usize>]) -> Result<TokenStream, String> {
+    if let Some((&len, dims)) = dims.split_first() {
+        let len = len.ok_or_else(|| "Runtime-sized arrays can't be checked".to_owned())?;
+        let elem = type_info(module, ty, dims)?;
+        return Ok(quote!(glsl_layout::reflect::TypeInfo::Array(&#elem, #len)));
+    }
+
+    Ok(match ty {
+        Type::Scalar(scalar) => {
+            let scalar = scalar_type(*scalar);
+            quote!(glsl_layout::reflect::TypeInfo::Scalar(#scalar))
+        }
+        Type::Vector(scalar, len) => {
+            let scalar = scalar_type(*scalar);
+            quote!(glsl_layout::reflect::TypeInfo::Vector(#scalar, #len))
+        }
+        Type::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => {
+            let scalar = scalar_type(*scalar);
+            quote!(glsl_layout::reflect::TypeInfo::Matrix {
+                scalar: #scalar,
+                columns: #columns,
+                rows: #rows,
+            })
+        }
+        Type::Struct(name) => {
+            let info = module.find_struct(name).unwrap();
+            let members = info
+                .members
+                .iter()
+                .map(|member| {
+                    let ty = type_info(module, &member.ty, &member.dims)?;
+                    let name = &member.name;
+                    Ok(quote!(glsl_layout::reflect::MemberInfo::new(#name, &#ty)))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            quote! {
+                glsl_layout::reflect::TypeInfo::Struct(
+                    &glsl_layout::reflect::StructInfo::new(#name, &[#(#members,)*])
+                )
+            }
+        }
+    })
+}
+
+fn scalar_type(scalar: Scalar) -> TokenStream {
+    match scalar {
+        Scalar::Bool => quote!(glsl_layout::reflect::ScalarType::Bool),
+        Scalar::Int => quote!(glsl_layout::reflect::ScalarType::Int),
+        Scalar::Uint => quote!(glsl_layout::reflect::ScalarType::Uint),
+        Scalar::Float => quote!(glsl_layout::reflect::ScalarType::Float),
+        Scalar::Double => quote!(glsl_layout::reflect::ScalarType::Double),
+    }
+}
+
+/// GLSL declaration of a member, e.g. `vec4 colors[4]`.
+fn declaration(ty: &Type, dims: &[Option<usize>], name: &str) -> String {
+    let mut declaration = format!("{} {}", ty.name(), name);
+    for len in dims {
+        match len {
+            Some(len) => declaration += &format!("[{}]", len),
+            None => declaration += "[]",
+        }
+    }
+    declaration
+}
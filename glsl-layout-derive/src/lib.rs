@@ -1,14 +1,18 @@
 #![recursion_limit = "128"]
 
+extern crate glsl_layout_parser;
 extern crate proc_macro;
 extern crate proc_macro2;
 extern crate syn;
 #[macro_use]
 extern crate quote;
 
+mod block;
+mod check;
+
 use proc_macro2::Span;
 
-#[proc_macro_derive(Uniform)]
+#[proc_macro_derive(Uniform, attributes(glsl))]
 pub fn uniform(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = proc_macro2::TokenStream::from(input);
 
@@ -17,6 +21,16 @@ pub fn uniform(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     proc_macro::TokenStream::from(impl_uniform(&ast))
 }
 
+/// Generate structures with `derive(Uniform)` from GLSL declarations of structures and blocks.
+#[proc_macro]
+pub fn glsl_block(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+
+    let output = block::glsl_block(input).unwrap_or_else(|err| err.to_compile_error());
+
+    proc_macro::TokenStream::from(output)
+}
+
 fn impl_uniform(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
     let name = &ast.ident;
 
@@ -30,19 +44,100 @@ fn impl_uniform(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
         _ => panic!(),
     };
 
-    let aligned_fields = fields.iter().flat_map(|field| {
-        let (a, f) = aligned_field(field);
+    let attrs = StructAttrs::parse(ast);
+    let members: Vec<Member> = fields.iter().flat_map(members).collect();
+
+    let check = match &attrs.check {
+        Some(path) => {
+            let block = attrs.block.clone().unwrap_or_else(|| name.to_string());
+            check::check(name, path, &block, &attrs.packings, &members)
+                .unwrap_or_else(|err| err.to_compile_error())
+        }
+        None => quote!(),
+    };
+
+    let aligned_fields = members.iter().flat_map(|member| {
+        let (a, f) = aligned_field(member);
         vec![a, f]
     });
 
-    let field_names = fields.iter().map(|field| field.ident.as_ref().unwrap());
-    let field_names2 = fields.iter().map(|field| field.ident.as_ref().unwrap());
+    let member_names = members.iter().map(|member| &member.ident);
+    let member_std140 = members.iter().map(|member| &member.std140);
+
+    let member_infos = members.iter().map(|member| {
+        let name = member.ident.to_string();
+        let name = name.trim_start_matches("r#");
+        let ty = &member.ty;
+        let packed = if member.packed {
+            Some(quote!(.packed()))
+        } else {
+            None
+        };
+        quote! {
+            glsl_layout::reflect::MemberInfo::new(
+                #name,
+                &<#ty as glsl_layout::Uniform>::TYPE_INFO,
+            )#packed
+        }
+    });
+
+    let writes = members.iter().map(|member| {
+        let member_name = &member.ident;
+        let ty = &member.ty;
+        let write = &member.write;
+        quote! {
+            let offset = ::core::mem::offset_of!(#rname, #member_name);
+            out[end..offset].fill(0);
+            {
+                let out = &mut out[offset..];
+                #write;
+            }
+            let end = offset + <#ty as glsl_layout::Uniform>::STD140_SIZE;
+        }
+    });
+
+    let std140_writes = members.iter().map(|member| {
+        let member_name = &member.ident;
+        let ty = &member.ty;
+        quote! {
+            let offset = ::core::mem::offset_of!(#rname, #member_name);
+            out[end..offset].fill(0);
+            glsl_layout::Uniform::write_std140(&self.#member_name, &mut out[offset..]);
+            let end = offset + <#ty as glsl_layout::Uniform>::STD140_SIZE;
+        }
+    });
+
+    let representations = attrs.layouts.into_iter().map(|(rules, rules_name)| {
+        let repr = format_ident!("Layout{}{}", rules_name, name);
+        let doc = format!(
+            "Bytes of `{}` laid out with `{}` rules. Aligned to 1 byte.",
+            name, rules_name
+        );
+        quote! {
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug)]
+            pub struct #repr([u8; <#name as glsl_layout::Layout<#rules>>::SIZE]);
 
-    let dummy = format_ident!("_GLSL_LAYOUT_{}", name);
+            impl AsRef<[u8]> for #repr {
+                fn as_ref(&self) -> &[u8] {
+                    &self.0
+                }
+            }
+
+            impl glsl_layout::Represent<#rules> for #name {
+                type Repr = #repr;
+
+                fn represent(&self) -> #repr {
+                    let mut repr = #repr([0; <#name as glsl_layout::Layout<#rules>>::SIZE]);
+                    glsl_layout::Layout::<#rules>::write_layout(self, &mut repr.0);
+                    repr
+                }
+            }
+        }
+    });
 
     quote! {
-        #[allow(bad_style)]
-        const #dummy: () = {
+        const _: () = {
             #[repr(C, align(16))]
             #[derive(Clone, Copy, Debug, Default)]
             pub struct #rname {#(
@@ -54,45 +149,303 @@ fn impl_uniform(ast: &syn::DeriveInput) -> proc_macro2::TokenStream {
             impl glsl_layout::Uniform for #rname {
                 type Align = glsl_layout::align::Align16;
                 type Std140 = #rname;
+                const TYPE_INFO: glsl_layout::reflect::TypeInfo =
+                    <#name as glsl_layout::Uniform>::TYPE_INFO;
 
                 fn std140(&self) -> #rname {
                     self.clone()
                 }
+
+                fn write_std140(&self, out: &mut [u8]) {
+                    let out = &mut out[..<Self as glsl_layout::Uniform>::STD140_SIZE];
+                    let end = 0;
+                    #(#std140_writes)*
+                    out[end..].fill(0);
+                }
             }
 
             impl glsl_layout::Uniform for #name {
                 type Align = glsl_layout::align::Align16;
                 type Std140 = #rname;
+                const TYPE_INFO: glsl_layout::reflect::TypeInfo =
+                    glsl_layout::reflect::TypeInfo::Struct(&glsl_layout::reflect::StructInfo::new(
+                        stringify!(#name),
+                        &[#(#member_infos,)*],
+                    ));
 
                 fn std140(&self) -> #rname {
                     #rname {
-                        #(#field_names: self.#field_names2.std140(),)*
+                        #(#member_names: #member_std140,)*
                         ..Default::default()
                     }
                 }
+
+                fn write_std140(&self, out: &mut [u8]) {
+                    let out = &mut out[..<Self as glsl_layout::Uniform>::STD140_SIZE];
+                    let end = 0;
+                    #(#writes)*
+                    out[end..].fill(0);
+                }
             }
+
+            #(#representations)*
+
+            #check
+        };
+    }
+}
+
+/// Member of `std140` representation.
+/// Fields that are `BoundedArray`s with `#[glsl(count = "...")]`
+/// or `Option`s with `#[glsl(flag = "...")]` become two members.
+struct Member {
+    ident: syn::Ident,
+    vis: syn::Visibility,
+    /// `Uniform` type of the member.
+    ty: syn::Type,
+    /// Statement that writes `std140` representation of the member into `out`.
+    write: proc_macro2::TokenStream,
+    /// Expression that yields `std140` representation of the member.
+    std140: proc_macro2::TokenStream,
+    packed: bool,
+}
+
+fn members(field: &syn::Field) -> Vec<Member> {
+    let attrs = FieldAttrs::parse(field);
+    let ident = field.ident.clone().unwrap();
+    let ty = &field.ty;
+
+    if let Some(flag) = attrs.flag {
+        let flag = Member {
+            ident: syn::Ident::new(&flag, Span::call_site()),
+            vis: field.vis.clone(),
+            ty: syn::parse_quote!(glsl_layout::boolean),
+            write: quote!(glsl_layout::Uniform::write_std140(
+                &glsl_layout::Optional::flag(&self.#ident),
+                out
+            )),
+            std140: quote!(glsl_layout::Optional::flag(&self.#ident)),
+            packed: false,
+        };
+        let value = Member {
+            ident: ident.clone(),
+            vis: field.vis.clone(),
+            ty: syn::parse_quote!(<#ty as glsl_layout::Optional>::Value),
+            write: quote!(glsl_layout::Optional::write_value(&self.#ident, out)),
+            std140: quote!(glsl_layout::Optional::value_std140(&self.#ident)),
+            packed: attrs.packed,
         };
+        return vec![flag, value];
+    }
+
+    let count = match attrs.count {
+        Some(count) => count,
+        None => {
+            return vec![Member {
+                ident: ident.clone(),
+                vis: field.vis.clone(),
+                ty: ty.clone(),
+                write: quote!(glsl_layout::Uniform::write_std140(&self.#ident, out)),
+                std140: quote!(glsl_layout::Uniform::std140(&self.#ident)),
+                packed: attrs.packed,
+            }]
+        }
+    };
+
+    let count = Member {
+        ident: syn::Ident::new(&count, Span::call_site()),
+        vis: field.vis.clone(),
+        ty: syn::parse_quote!(glsl_layout::uint),
+        write: quote!(glsl_layout::Uniform::write_std140(
+            &glsl_layout::Bounded::count(&self.#ident),
+            out
+        )),
+        std140: quote!(glsl_layout::Bounded::count(&self.#ident)),
+        packed: false,
+    };
+    let array = Member {
+        ident: ident.clone(),
+        vis: field.vis.clone(),
+        ty: syn::parse_quote!(<#ty as glsl_layout::Bounded>::Array),
+        write: quote!(glsl_layout::Bounded::write_array(&self.#ident, out)),
+        std140: quote!(glsl_layout::Bounded::array_std140(&self.#ident)),
+        packed: attrs.packed,
+    };
+
+    if attrs.count_after {
+        vec![array, count]
+    } else {
+        vec![count, array]
     }
 }
 
-fn aligned_field(field: &syn::Field) -> (syn::Field, syn::Field) {
-    let name = field.ident.as_ref().unwrap();
+fn aligned_field(member: &Member) -> (syn::Field, syn::Field) {
     let align = syn::Field {
-        ty: syn::Type::Path(align_type_for(&field.ty)),
-        ident: Some(format_ident!("_align_{}", name)),
+        ty: syn::Type::Path(align_type_for(&member.ty)),
+        ident: Some(format_ident!("_align_{}", member.ident)),
         attrs: Vec::new(),
         vis: syn::Visibility::Inherited,
         colon_token: Some(Default::default()),
     };
 
     let std140 = syn::Field {
-        ty: syn::Type::Path(std140_type_for(&field.ty)),
-        ..field.clone()
+        ty: syn::Type::Path(std140_type_for(&member.ty)),
+        ident: Some(member.ident.clone()),
+        attrs: Vec::new(),
+        vis: member.vis.clone(),
+        colon_token: Some(Default::default()),
     };
 
     (align, std140)
 }
 
+/// Arguments of all `#[glsl(...)]` attributes.
+fn glsl_attributes(attrs: &[syn::Attribute]) -> Vec<syn::NestedMeta> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("glsl"))
+        .flat_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => list.nested.into_iter(),
+            _ => panic!("Expected `#[glsl(...)]`"),
+        })
+        .collect()
+}
+
+/// Arguments of `#[glsl(...)]` attributes of a field.
+#[derive(Default)]
+struct FieldAttrs {
+    /// `#[glsl(packed)]`.
+    packed: bool,
+    /// `#[glsl(count = "name")]`.
+    count: Option<String>,
+    /// `#[glsl(count_after)]`.
+    count_after: bool,
+    /// `#[glsl(flag = "name")]`.
+    flag: Option<String>,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> Self {
+        let mut attrs = FieldAttrs::default();
+        for nested in glsl_attributes(&field.attrs) {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("packed") => {
+                    attrs.packed = true
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("count_after") => {
+                    attrs.count_after = true
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(name),
+                    ..
+                })) if path.is_ident("count") => attrs.count = Some(name.value()),
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(name),
+                    ..
+                })) if path.is_ident("flag") => attrs.flag = Some(name.value()),
+                _ => panic!("Unknown `glsl` attribute"),
+            }
+        }
+        if attrs.count_after && attrs.count.is_none() {
+            panic!("`#[glsl(count_after)]` requires `#[glsl(count = \"...\")]`");
+        }
+        if attrs.count.is_some() && attrs.flag.is_some() {
+            panic!("`#[glsl(count = \"...\")]` and `#[glsl(flag = \"...\")]` are exclusive");
+        }
+        attrs
+    }
+}
+
+/// Arguments of `#[glsl(...)]` attributes of a structure.
+#[derive(Default)]
+struct StructAttrs {
+    /// Layout rules listed in `#[glsl(layouts(...))]` except `std140`,
+    /// with names for their representations.
+    layouts: Vec<(syn::Path, syn::Ident)>,
+    /// Names of all layout rules listed in `#[glsl(layouts(...))]`.
+    packings: Vec<String>,
+    /// `#[glsl(check = "path")]`.
+    check: Option<syn::LitStr>,
+    /// `#[glsl(block = "name")]`.
+    block: Option<String>,
+}
+
+impl StructAttrs {
+    fn parse(ast: &syn::DeriveInput) -> Self {
+        let mut attrs = StructAttrs::default();
+        for nested in glsl_attributes(&ast.attrs) {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("layouts") => {
+                    attrs
+                        .packings
+                        .extend(list.nested.iter().filter_map(|nested| match nested {
+                            syn::NestedMeta::Meta(syn::Meta::Path(path)) => {
+                                path.get_ident().map(|ident| ident.to_string())
+                            }
+                            _ => None,
+                        }));
+                    attrs
+                        .layouts
+                        .extend(list.nested.into_iter().filter_map(layout))
+                }
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(file),
+                    ..
+                })) if path.is_ident("check") => attrs.check = Some(file),
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    lit: syn::Lit::Str(name),
+                    ..
+                })) if path.is_ident("block") => attrs.block = Some(name.value()),
+                _ => panic!("Unknown `glsl` attribute"),
+            }
+        }
+        if attrs.block.is_some() && attrs.check.is_none() {
+            panic!("`#[glsl(block = \"...\")]` requires `#[glsl(check = \"...\")]`");
+        }
+        attrs
+    }
+}
+
+/// Layout rules from `#[glsl(layouts(...))]` with name for representation.
+/// `std140` is skipped.
+fn layout(nested: syn::NestedMeta) -> Option<(syn::Path, syn::Ident)> {
+    let path = match nested {
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) => path,
+        _ => panic!("Expected layout rules in `#[glsl(layouts(...))]`"),
+    };
+
+    if path.is_ident("std140") {
+        return None;
+    }
+
+    let known = path
+        .get_ident()
+        .and_then(|ident| match ident.to_string().as_str() {
+            "std430" => Some("Std430"),
+            "scalar" => Some("Scalar"),
+            "hlsl" => Some("Hlsl"),
+            "msl" => Some("Msl"),
+            "wgsl_uniform" => Some("WgslUniform"),
+            "wgsl_storage" => Some("WgslStorage"),
+            _ => None,
+        });
+
+    Some(match known {
+        Some(rules) => {
+            let rules = syn::Ident::new(rules, Span::call_site());
+            (syn::parse_quote!(glsl_layout::layout::#rules), rules)
+        }
+        None => {
+            let rules = path.segments.last().unwrap().ident.clone();
+            (path, rules)
+        }
+    })
+}
+
 fn align_type_for(aligned: &syn::Type) -> syn::TypePath {
     use std::iter::once;
     syn::TypePath {
@@ -0,0 +1,974 @@
+//! Parser of GLSL structure and interface block declarations.
+//!
+//! Only declarations that affect memory layout are parsed.
+//! Functions, global variables and preprocessor directives other than `#define` are skipped.
+//! Integer constants from `#define` can be used as array lengths.
+//! Files included with `#include "path"` are resolved by `Module::load`.
+
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Scalar types of glsl.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scalar {
+    /// `bool`.
+    Bool,
+    /// `int`.
+    Int,
+    /// `uint`.
+    Uint,
+    /// `float`.
+    Float,
+    /// `double`.
+    Double,
+}
+
+impl Scalar {
+    /// Name of the scalar type.
+    pub fn name(self) -> &'static str {
+        match self {
+            Scalar::Bool => "bool",
+            Scalar::Int => "int",
+            Scalar::Uint => "uint",
+            Scalar::Float => "float",
+            Scalar::Double => "double",
+        }
+    }
+
+    /// Prefix of vector and matrix types with components of this type.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Scalar::Bool => "b",
+            Scalar::Int => "i",
+            Scalar::Uint => "u",
+            Scalar::Float => "",
+            Scalar::Double => "d",
+        }
+    }
+}
+
+/// Type of member without array dimensions.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Type {
+    /// Scalar.
+    Scalar(Scalar),
+
+    /// Vector of 2, 3 or 4 scalars.
+    Vector(Scalar, usize),
+
+    /// Matrix of `float`s or `double`s.
+    Matrix {
+        /// Type of matrix elements.
+        scalar: Scalar,
+        /// Number of columns.
+        columns: usize,
+        /// Number of rows.
+        rows: usize,
+    },
+
+    /// Structure with given name.
+    Struct(String),
+}
+
+impl Type {
+    /// Builtin type with given name.
+    pub fn builtin(name: &str) -> Option<Type> {
+        let scalar = |prefix: &str| match prefix {
+            "b" => Some(Scalar::Bool),
+            "i" => Some(Scalar::Int),
+            "u" => Some(Scalar::Uint),
+            "" => Some(Scalar::Float),
+            "d" => Some(Scalar::Double),
+            _ => None,
+        };
+        let size = |digit: &str| match digit {
+            "2" => Some(2),
+            "3" => Some(3),
+            "4" => Some(4),
+            _ => None,
+        };
+
+        match name {
+            "bool" => return Some(Type::Scalar(Scalar::Bool)),
+            "int" => return Some(Type::Scalar(Scalar::Int)),
+            "uint" => return Some(Type::Scalar(Scalar::Uint)),
+            "float" => return Some(Type::Scalar(Scalar::Float)),
+            "double" => return Some(Type::Scalar(Scalar::Double)),
+            _ => {}
+        }
+
+        if let Some(index) = name.find("vec") {
+            let scalar = scalar(&name[..index])?;
+            return Some(Type::Vector(scalar, size(&name[index + 3..])?));
+        }
+
+        if let Some(index) = name.find("mat") {
+            let scalar = scalar(&name[..index])
+                .filter(|&scalar| scalar == Scalar::Float || scalar == Scalar::Double)?;
+            let dims = &name[index + 3..];
+            let (columns, rows) = match dims.split_once('x') {
+                Some((columns, rows)) => (size(columns)?, size(rows)?),
+                None => (size(dims)?, size(dims)?),
+            };
+            return Some(Type::Matrix {
+                scalar,
+                columns,
+                rows,
+            });
+        }
+
+        None
+    }
+
+    /// Name of the type.
+    pub fn name(&self) -> String {
+        match self {
+            Type::Scalar(scalar) => scalar.name().to_owned(),
+            Type::Vector(scalar, len) => format!("{}vec{}", scalar.prefix(), len),
+            Type::Matrix {
+                scalar,
+                columns,
+                rows,
+            } if columns == rows => format!("{}mat{}", scalar.prefix(), columns),
+            Type::Matrix {
+                scalar,
+                columns,
+                rows,
+            } => format!("{}mat{}x{}", scalar.prefix(), columns, rows),
+            Type::Struct(name) => name.clone(),
+        }
+    }
+}
+
+/// Member of structure or block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    /// Name of the member.
+    pub name: String,
+
+    /// Type of the member or its elements.
+    pub ty: Type,
+
+    /// Array dimensions, outermost first.
+    /// `None` is length of runtime-sized array.
+    pub dims: Vec<Option<usize>>,
+}
+
+/// Structure declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Struct {
+    /// Name of the structure.
+    pub name: String,
+
+    /// Line of the declaration, starting with 1.
+    pub line: usize,
+
+    /// Members in declaration order.
+    pub members: Vec<Member>,
+}
+
+/// Storage qualifier of interface block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Storage {
+    /// `uniform` block.
+    Uniform,
+    /// `buffer` block.
+    Buffer,
+}
+
+/// Interface block declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    /// Storage qualifier.
+    pub storage: Storage,
+
+    /// Memory layout qualifier, i.e. `std140`, `std430` or `scalar`.
+    pub packing: Option<String>,
+
+    /// Binding point.
+    pub binding: Option<u32>,
+
+    /// Name of the block.
+    pub name: String,
+
+    /// Line of the declaration, starting with 1.
+    pub line: usize,
+
+    /// Name of the block instance.
+    pub instance: Option<String>,
+
+    /// Members in declaration order.
+    pub members: Vec<Member>,
+}
+
+impl Block {
+    /// Memory layout of the block.
+    /// Uniform blocks use `std140` and buffer blocks use `std430` unless specified otherwise.
+    pub fn packing(&self) -> &str {
+        match (&self.packing, self.storage) {
+            (Some(packing), _) => packing,
+            (None, Storage::Uniform) => "std140",
+            (None, Storage::Buffer) => "std430",
+        }
+    }
+}
+
+/// Declarations of GLSL source.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Module {
+    /// Integer constants defined with `#define`.
+    pub constants: Vec<(String, usize)>,
+
+    /// Structures in declaration order.
+    pub structs: Vec<Struct>,
+
+    /// Uniform and buffer blocks in declaration order.
+    pub blocks: Vec<Block>,
+}
+
+impl Module {
+    /// Parse declarations of GLSL source.
+    pub fn parse(source: &str) -> Result<Module, Error> {
+        let mut module = Module::default();
+        module.extend(source)?;
+        Ok(module)
+    }
+
+    /// Parse declarations of another GLSL source, i.e. included one.
+    /// Structures and constants declared before can be used in it.
+    pub fn extend(&mut self, source: &str) -> Result<(), Error> {
+        let tokens = preprocess(source, &mut self.constants)?;
+        Parser {
+            tokens: &tokens,
+            pos: 0,
+            module: self,
+        }
+        .parse()
+    }
+
+    /// Parse declarations of GLSL file after files it includes with `#include "path"`,
+    /// resolved relative to the including file.
+    /// Files whose canonical paths are in `loaded` are skipped, loaded files are added to it.
+    /// `parsed` is called with path of every file right after it is parsed.
+    pub fn load(
+        &mut self,
+        path: &Path,
+        loaded: &mut Vec<PathBuf>,
+        parsed: &mut dyn FnMut(&Module, &Path),
+    ) -> Result<(), LoadError> {
+        let io = |error| LoadError::Io {
+            path: path.to_owned(),
+            error,
+        };
+        let canonical = path.canonicalize().map_err(io)?;
+        if loaded.contains(&canonical) {
+            return Ok(());
+        }
+        loaded.push(canonical);
+
+        let source = fs::read_to_string(path).map_err(io)?;
+        for include in includes(&source) {
+            let include = path.parent().unwrap_or(Path::new("")).join(include);
+            self.load(&include, loaded, parsed)?;
+        }
+
+        self.extend(&source).map_err(|error| LoadError::Parse {
+            path: path.to_owned(),
+            error,
+        })?;
+        parsed(self, path);
+        Ok(())
+    }
+
+    /// Find structure by name.
+    pub fn find_struct(&self, name: &str) -> Option<&Struct> {
+        self.structs.iter().find(|s| s.name == name)
+    }
+
+    /// Find block by its name or instance name.
+    pub fn find_block(&self, name: &str) -> Option<&Block> {
+        self.blocks
+            .iter()
+            .find(|b| b.name == name || b.instance.as_deref() == Some(name))
+    }
+
+    /// Check if Rust structure generated for members can derive `Default`.
+    /// It is implemented only for arrays of up to 32 elements,
+    /// thus members of structure types are checked recursively.
+    /// Runtime-sized arrays are ignored.
+    pub fn derives_default(&self, members: &[Member]) -> bool {
+        members.iter().all(|member| {
+            member
+                .dims
+                .iter()
+                .all(|&len| len.is_none_or(|len| len <= 32))
+                && match &member.ty {
+                    Type::Struct(name) => self
+                        .find_struct(name)
+                        .is_none_or(|s| self.derives_default(&s.members)),
+                    _ => true,
+                }
+        })
+    }
+
+    /// Structures used by members, directly or not, in declaration order.
+    pub fn dependencies(&self, members: &[Member]) -> Vec<&Struct> {
+        fn collect<'a>(module: &'a Module, members: &[Member], used: &mut Vec<&'a str>) {
+            for member in members {
+                if let Type::Struct(name) = &member.ty {
+                    if !used.contains(&name.as_str()) {
+                        if let Some(s) = module.find_struct(name) {
+                            used.push(&s.name);
+                            collect(module, &s.members, used);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut used = Vec::new();
+        collect(self, members, &mut used);
+        self.structs
+            .iter()
+            .filter(|s| used.contains(&s.name.as_str()))
+            .collect()
+    }
+}
+
+/// Error of GLSL parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Error {
+    /// Line of the source, starting with 1.
+    pub line: usize,
+
+    /// Description of the error.
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Error of GLSL file loading.
+#[derive(Debug)]
+pub enum LoadError {
+    /// File can't be read.
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying error.
+        error: io::Error,
+    },
+
+    /// File can't be parsed.
+    Parse {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying error.
+        error: Error,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            LoadError::Parse { path, error } => {
+                write!(f, "{}:{}: {}", path.display(), error.line, error.message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io { error, .. } => Some(error),
+            LoadError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Paths of files included with `#include "path"`.
+fn includes(source: &str) -> impl Iterator<Item = &str> {
+    source.lines().filter_map(|line| {
+        let directive = line.trim_start().strip_prefix('#')?.trim_start();
+        let path = directive.strip_prefix("include")?.trim();
+        path.strip_prefix('"')?.strip_suffix('"')
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(String),
+    Punct(char),
+}
+
+type Tokens = Vec<(Token, usize)>;
+
+/// Remove comments, handle preprocessor directives and split source into tokens.
+fn preprocess(source: &str, constants: &mut Vec<(String, usize)>) -> Result<Tokens, Error> {
+    let source = strip_comments(source);
+    let mut tokens = Vec::new();
+    let mut lines = source.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let mut line = line.to_owned();
+        while line.ends_with('\\') {
+            line.pop();
+            match lines.next() {
+                Some((_, next)) => line.push_str(next),
+                None => break,
+            }
+        }
+
+        let line_tokens = tokenize(&line, index + 1)?;
+        match line_tokens.first() {
+            Some((Token::Punct('#'), _)) => define(&line_tokens[1..], constants),
+            _ => tokens.extend(line_tokens),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Replace comments with spaces, keeping line breaks.
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|&c| c != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut last = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        result.push('\n');
+                    }
+                    if last == '*' && c == '/' {
+                        break;
+                    }
+                    last = c;
+                }
+                result.push(' ');
+            }
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn tokenize(line: &str, number: usize) -> Result<Tokens, Error> {
+    let mut tokens = Vec::new();
+    let mut chars = line.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        if c.is_alphanumeric() || c == '_' {
+            let mut end = start + c.len_utf8();
+            while let Some(&(index, c)) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' || c == '.' && is_number(&line[start..]) {
+                    end = index + c.len_utf8();
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            let text = line[start..end].to_owned();
+            if c.is_ascii_digit() {
+                tokens.push((Token::Number(text), number));
+            } else {
+                tokens.push((Token::Ident(text), number));
+            }
+        } else if c.is_ascii_punctuation() {
+            tokens.push((Token::Punct(c), number));
+        } else {
+            return Err(Error {
+                line: number,
+                message: format!("Unexpected character `{}`", c),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_number(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// Parse integer literal.
+fn parse_number(text: &str) -> Option<usize> {
+    let text = text.trim_end_matches(['u', 'U']);
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => usize::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+fn constant(constants: &[(String, usize)], name: &str) -> Option<usize> {
+    constants
+        .iter()
+        .rev()
+        .find(|(constant, _)| constant == name)
+        .map(|&(_, value)| value)
+}
+
+/// Remember integer constant defined by directive.
+fn define(tokens: &[(Token, usize)], constants: &mut Vec<(String, usize)>) {
+    let tokens: Vec<&Token> = tokens.iter().map(|(token, _)| token).collect();
+    let (name, value) = match tokens.as_slice() {
+        [Token::Ident(directive), Token::Ident(name), value @ ..] if directive == "define" => {
+            (name, value)
+        }
+        _ => return,
+    };
+
+    let value = match value {
+        [Token::Number(number)] | [Token::Punct('('), Token::Number(number), Token::Punct(')')] => {
+            parse_number(number)
+        }
+        [Token::Ident(other)] => constant(constants, other),
+        _ => None,
+    };
+    if let Some(value) = value {
+        constants.push((name.clone(), value));
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [(Token, usize)],
+    pos: usize,
+    module: &'a mut Module,
+}
+
+impl Parser<'_> {
+    fn parse(&mut self) -> Result<(), Error> {
+        while self.pos < self.tokens.len() {
+            if self.is_ident("struct") {
+                self.parse_struct()?;
+            } else {
+                self.parse_statement()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn error<T>(&self, message: String) -> Result<T, Error> {
+        let line = self
+            .tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |&(_, line)| line);
+        Err(Error { line, message })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn is_ident(&self, ident: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(name)) if name == ident)
+    }
+
+    fn expect_punct(&mut self, punct: char) -> Result<(), Error> {
+        match self.peek() {
+            Some(&Token::Punct(c)) if c == punct => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => self.error(format!("Expected `{}`", punct)),
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String, Error> {
+        match self.peek() {
+            Some(Token::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => self.error("Expected identifier".to_owned()),
+        }
+    }
+
+    /// Skip tokens up to and including `;` outside of braces.
+    fn skip_declarators(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        let end = self.statement_end(start, &[';'])?;
+        self.pos = end + 1;
+        Ok(())
+    }
+
+    /// Position of first of `ends` at depth 0, starting from `start`.
+    fn statement_end(&self, start: usize, ends: &[char]) -> Result<usize, Error> {
+        let mut depth = 0usize;
+        for (index, (token, line)) in self.tokens.iter().enumerate().skip(start) {
+            match *token {
+                Token::Punct(c) if depth == 0 && ends.contains(&c) => return Ok(index),
+                Token::Punct('(' | '[' | '{') => depth += 1,
+                Token::Punct(')' | ']' | '}') => {
+                    depth = depth.checked_sub(1).ok_or_else(|| Error {
+                        line: *line,
+                        message: "Unbalanced brackets".to_owned(),
+                    })?
+                }
+                _ => {}
+            }
+        }
+        self.error("Unexpected end of source".to_owned())
+    }
+
+    fn parse_struct(&mut self) -> Result<(), Error> {
+        let line = self.tokens[self.pos].1;
+        self.pos += 1;
+        let name = self.expect_ident()?;
+        let members = self.parse_members()?;
+        self.skip_declarators()?;
+        self.module.structs.push(Struct {
+            name,
+            line,
+            members,
+        });
+        Ok(())
+    }
+
+    fn parse_statement(&mut self) -> Result<(), Error> {
+        let start = self.pos;
+        let end = self.statement_end(start, &[';', '{'])?;
+        let statement = &self.tokens[start..end];
+        self.pos = end;
+        if self.tokens[end].0 == Token::Punct(';') {
+            self.pos += 1;
+            return Ok(());
+        }
+
+        let storage = statement.iter().find_map(|(token, _)| match token {
+            Token::Ident(name) if name == "uniform" => Some(Storage::Uniform),
+            Token::Ident(name) if name == "buffer" => Some(Storage::Buffer),
+            _ => None,
+        });
+        let name = match statement.last() {
+            Some((Token::Ident(name), _)) => Some(name.clone()),
+            _ => None,
+        };
+        let (storage, name) = match (storage, name) {
+            (Some(storage), Some(name)) => (storage, name),
+            _ => {
+                // Function or other block that doesn't matter.
+                self.pos += 1;
+                let close = self.statement_end(self.pos, &['}'])?;
+                self.pos = close + 1;
+                if self.peek() == Some(&Token::Punct(';')) {
+                    self.pos += 1;
+                }
+                return Ok(());
+            }
+        };
+
+        let line = statement[0].1;
+        let (packing, binding) = layout_qualifiers(statement);
+        let members = self.parse_members()?;
+        let instance = match self.peek() {
+            Some(Token::Ident(_)) => Some(self.expect_ident()?),
+            _ => None,
+        };
+        self.skip_declarators()?;
+        self.module.blocks.push(Block {
+            storage,
+            packing,
+            binding,
+            name,
+            line,
+            instance,
+            members,
+        });
+        Ok(())
+    }
+
+    /// Parse members between braces.
+    fn parse_members(&mut self) -> Result<Vec<Member>, Error> {
+        self.expect_punct('{')?;
+        let mut members = Vec::new();
+        while self.peek() != Some(&Token::Punct('}')) {
+            let start = self.pos;
+            let end = self.statement_end(start, &[';', '}'])?;
+            self.pos = start;
+            if self.tokens[end].0 != Token::Punct(';') {
+                self.pos = end;
+                return self.error("Expected `;`".to_owned());
+            }
+            self.parse_declaration(&self.tokens[start..end], &mut members)?;
+            self.pos = end + 1;
+        }
+        self.pos += 1;
+        Ok(members)
+    }
+
+    /// Parse member declaration with one or more declarators.
+    fn parse_declaration(
+        &self,
+        tokens: &[(Token, usize)],
+        members: &mut Vec<Member>,
+    ) -> Result<(), Error> {
+        let tokens = without_layout(tokens);
+        let mut segments = split_declarators(&tokens);
+
+        // `qualifiers type[dims] name[dims]`.
+        let first = segments.remove(0);
+        let (name_index, declarator_dims) = declarator(first);
+        let name_index = match name_index {
+            Some(index) if index > 0 => index,
+            _ => return self.error("Expected member declaration".to_owned()),
+        };
+        let mut type_end = name_index;
+        while type_end > 0 && first[type_end - 1].0 == Token::Punct(']') {
+            type_end = first[..type_end]
+                .iter()
+                .rposition(|(token, _)| *token == Token::Punct('['))
+                .unwrap_or(0);
+        }
+        let ty = match type_end.checked_sub(1).map(|index| &first[index].0) {
+            Some(Token::Ident(name)) => self.resolve_type(name)?,
+            _ => return self.error("Expected type".to_owned()),
+        };
+        let type_dims = self.dims(&first[type_end..name_index])?;
+
+        for (index, segment) in std::iter::once(&first[name_index..])
+            .chain(segments)
+            .enumerate()
+        {
+            let name = match segment.first() {
+                Some((Token::Ident(name), _)) => name.clone(),
+                _ => return self.error("Expected member name".to_owned()),
+            };
+            let dims = if index == 0 {
+                declarator_dims
+            } else {
+                &segment[1..]
+            };
+            let mut dims = self.dims(dims)?;
+            dims.extend(type_dims.iter().copied());
+            members.push(Member {
+                name,
+                ty: ty.clone(),
+                dims,
+            });
+        }
+        Ok(())
+    }
+
+    fn resolve_type(&self, name: &str) -> Result<Type, Error> {
+        if let Some(ty) = Type::builtin(name) {
+            return Ok(ty);
+        }
+        if self.module.find_struct(name).is_some() {
+            return Ok(Type::Struct(name.to_owned()));
+        }
+        self.error(format!("Unknown type `{}`", name))
+    }
+
+    /// Parse sequence of `[N]`.
+    fn dims(&self, tokens: &[(Token, usize)]) -> Result<Vec<Option<usize>>, Error> {
+        let mut dims = Vec::new();
+        let mut rest = tokens;
+        while !rest.is_empty() {
+            let (len, tail) = match rest {
+                [(Token::Punct('['), _), (Token::Punct(']'), _), tail @ ..] => (None, tail),
+                [(Token::Punct('['), _), (length, line), (Token::Punct(']'), _), tail @ ..] => {
+                    let len = match length {
+                        Token::Number(number) => parse_number(number),
+                        Token::Ident(name) => constant(&self.module.constants, name),
+                        Token::Punct(_) => None,
+                    };
+                    match len {
+                        Some(len) => (Some(len), tail),
+                        None => {
+                            return Err(Error {
+                                line: *line,
+                                message: "Array length must be integer constant".to_owned(),
+                            })
+                        }
+                    }
+                }
+                _ => return self.error("Expected array dimensions".to_owned()),
+            };
+            dims.push(len);
+            rest = tail;
+        }
+        Ok(dims)
+    }
+}
+
+/// Find `layout(...)` qualifier in statement and get packing and binding from it.
+fn layout_qualifiers(statement: &[(Token, usize)]) -> (Option<String>, Option<u32>) {
+    let mut packing = None;
+    let mut binding = None;
+    let start = statement
+        .iter()
+        .position(|(token, _)| *token == Token::Ident("layout".to_owned()));
+    if let Some(start) = start {
+        let qualifiers = statement[start + 1..]
+            .iter()
+            .take_while(|(token, _)| *token != Token::Punct(')'))
+            .map(|(token, _)| token)
+            .collect::<Vec<_>>();
+        for (index, token) in qualifiers.iter().enumerate() {
+            match token {
+                Token::Ident(name)
+                    if ["std140", "std430", "scalar", "shared", "packed"].contains(&&**name) =>
+                {
+                    packing = Some(name.clone())
+                }
+                Token::Ident(name) if name == "binding" => {
+                    if let Some(Token::Number(value)) = qualifiers.get(index + 2) {
+                        binding = parse_number(value).map(|value| value as u32);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    (packing, binding)
+}
+
+/// Remove `layout(...)` qualifiers.
+fn without_layout(tokens: &[(Token, usize)]) -> Vec<(Token, usize)> {
+    let mut result = Vec::new();
+    let mut iter = tokens.iter();
+    while let Some(token) = iter.next() {
+        if token.0 == Token::Ident("layout".to_owned()) {
+            for (token, _) in iter.by_ref() {
+                if *token == Token::Punct(')') {
+                    break;
+                }
+            }
+        } else {
+            result.push(token.clone());
+        }
+    }
+    result
+}
+
+/// Split declaration by commas outside of brackets.
+fn split_declarators(tokens: &[(Token, usize)]) -> Vec<&[(Token, usize)]> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut segments = Vec::new();
+    for (index, (token, _)) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('[' | '(') => depth += 1,
+            Token::Punct(']' | ')') => depth -= 1,
+            Token::Punct(',') if depth == 0 => {
+                segments.push(&tokens[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    segments.push(&tokens[start..]);
+    segments
+}
+
+/// Find name of the first declarator and its array dimensions.
+fn declarator(tokens: &[(Token, usize)]) -> (Option<usize>, &[(Token, usize)]) {
+    let mut end = tokens.len();
+    while end > 0 && tokens[end - 1].0 == Token::Punct(']') {
+        match tokens[..end]
+            .iter()
+            .rposition(|(token, _)| *token == Token::Punct('['))
+        {
+            Some(open) => end = open,
+            None => return (None, &[]),
+        }
+    }
+    match end.checked_sub(1).map(|index| &tokens[index].0) {
+        Some(Token::Ident(_)) => (Some(end - 1), &tokens[end..]),
+        _ => (None, &[]),
+    }
+}
+
+#[test]
+fn test_parse() {
+    let source = r#"
+#version 450
+#define MAX_LIGHTS 4
+#define COUNT MAX_LIGHTS
+#define SQUARE(x) ((x) * (x))
+
+struct Light {
+    vec3 position; // Position in world space.
+    float intensity;
+};
+
+/* Block of
+   camera parameters. */
+layout(std140, binding = 1) uniform Camera {
+    mat4 view;
+    highp mat3x4 proj;
+    Light lights[MAX_LIGHTS], extra;
+    float[2] weights[COUNT];
+} camera;
+
+layout(std430) readonly buffer Particles {
+    uint count;
+    dvec2 items[];
+};
+
+uniform sampler2D image;
+
+void main() {
+    if (true) { gl_Position = vec4(0.0); }
+}
+"#;
+
+    let module = Module::parse(source).unwrap();
+    assert_eq!(
+        module.constants,
+        [("MAX_LIGHTS".to_owned(), 4), ("COUNT".to_owned(), 4)]
+    );
+    assert_eq!(module.structs.len(), 1);
+    assert_eq!(module.structs[0].members[1].ty, Type::Scalar(Scalar::Float));
+
+    let camera = module.find_block("camera").unwrap();
+    assert_eq!(camera.name, "Camera");
+    assert_eq!(camera.packing(), "std140");
+    assert_eq!(camera.binding, Some(1));
+    assert_eq!(camera.line, 14);
+    let members = camera
+        .members
+        .iter()
+        .map(|member| (member.name.as_str(), member.ty.name(), member.dims.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        members,
+        [
+            ("view", "mat4".to_owned(), vec![]),
+            ("proj", "mat3x4".to_owned(), vec![]),
+            ("lights", "Light".to_owned(), vec![Some(4)]),
+            ("extra", "Light".to_owned(), vec![]),
+            ("weights", "float".to_owned(), vec![Some(4), Some(2)]),
+        ]
+    );
+    assert_eq!(module.dependencies(&camera.members).len(), 1);
+
+    let particles = module.find_block("Particles").unwrap();
+    assert_eq!(particles.storage, Storage::Buffer);
+    assert_eq!(particles.packing(), "std430");
+    assert_eq!(particles.members[1].dims, [None]);
+
+    let error = Module::parse("struct A {\n    vec5 b;\n};").unwrap_err();
+    assert_eq!(error.line, 2);
+    assert_eq!(error.message, "Unknown type `vec5`");
+}
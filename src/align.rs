@@ -1,3 +1,5 @@
+use crate::scalar::uint;
+
 #[repr(align(4))]
 #[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub struct Align4;
@@ -13,3 +15,34 @@ pub struct Align16;
 #[repr(align(32))]
 #[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub struct Align32;
+
+/// Explicit padding of `Option` around value with this alignment,
+/// so that its `std140` representation has no implicit padding.
+#[doc(hidden)]
+pub trait OptionPadding {
+    /// Padding between presence flag and the value.
+    type Head: Copy + Default;
+
+    /// Padding after the value of size equal to the alignment.
+    type Tail: Copy + Default;
+}
+
+impl OptionPadding for Align4 {
+    type Head = [uint; 0];
+    type Tail = [uint; 2];
+}
+
+impl OptionPadding for Align8 {
+    type Head = [uint; 1];
+    type Tail = [uint; 0];
+}
+
+impl OptionPadding for Align16 {
+    type Head = [uint; 3];
+    type Tail = [uint; 0];
+}
+
+impl OptionPadding for Align32 {
+    type Head = [uint; 7];
+    type Tail = [uint; 0];
+}
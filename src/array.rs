@@ -1,7 +1,9 @@
 use crate::align::Align16;
-use crate::uniform::{Std140, Uniform};
+use crate::reflect::TypeInfo;
+use crate::uniform::{IsStd140, Std140, Uniform};
 use std::{
     marker::PhantomData,
+    mem::size_of,
     slice::{Iter as SliceIter, IterMut as SliceIterMut},
 };
 
@@ -42,45 +44,79 @@ where
     }
 }
 
+/// Kind of glsl type that `Array` represents.
+pub trait ArrayKind: Copy + Default {
+    /// Array of float or double vectors is a matrix.
+    #[doc(hidden)]
+    const MATRIX: bool;
+}
+
+/// `Array` is glsl array.
+#[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Ord, Eq, Hash)]
+pub struct Elements;
+
+impl ArrayKind for Elements {
+    const MATRIX: bool = false;
+}
+
+/// `Array` is glsl matrix, i.e. array of its columns.
+#[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Ord, Eq, Hash)]
+pub struct Columns;
+
+impl ArrayKind for Columns {
+    const MATRIX: bool = true;
+}
+
 /// Array of `Element`s.
 /// This type implements useful traits for converting from unwrapped types.
-#[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Ord, Eq, Hash)]
+/// Matrices are arrays of `Columns`.
+#[derive(Clone, Copy, Debug, PartialOrd, PartialEq, Ord, Eq, Hash)]
 #[repr(C, align(16))]
-pub struct Array<T, A>(pub A, pub PhantomData<fn(T)>);
+pub struct Array<T, A, K = Elements>(pub A, pub PhantomData<fn(T, K)>);
+
+// Implemented for arrays of any length, unlike `Default` of arrays.
+impl<T, U, K, const N: usize> Default for Array<T, [U; N], K>
+where
+    U: Default,
+{
+    fn default() -> Self {
+        Array(std::array::from_fn(|_| U::default()), PhantomData)
+    }
+}
 
-impl<T, A> Array<T, A> {
+impl<T, A, K> Array<T, A, K> {
     pub fn new(array: A) -> Self {
         Array(array, PhantomData)
     }
 }
 
-impl<T, A> AsRef<A> for Array<T, A> {
+impl<T, A, K> AsRef<A> for Array<T, A, K> {
     fn as_ref(&self) -> &A {
         &self.0
     }
 }
 
-impl<T, A> AsMut<A> for Array<T, A> {
+impl<T, A, K> AsMut<A> for Array<T, A, K> {
     fn as_mut(&mut self) -> &mut A {
         &mut self.0
     }
 }
 
-impl<T, A> Array<T, A>
+impl<T, A, K> Array<T, A, K>
 where
     T: Uniform,
     A: AsMut<[Element<T>]> + AsRef<[Element<T>]>,
 {
-    pub fn iter(&self) -> ArrayIter<SliceIter<Element<T>>> {
+    pub fn iter(&self) -> ArrayIter<SliceIter<'_, Element<T>>> {
         ArrayIter(self.0.as_ref().iter())
     }
 
-    pub fn iter_mut(&mut self) -> ArrayIter<SliceIterMut<Element<T>>> {
+    pub fn iter_mut(&mut self) -> ArrayIter<SliceIterMut<'_, Element<T>>> {
         ArrayIter(self.0.as_mut().iter_mut())
     }
 }
 
-impl<'a, T, A> IntoIterator for &'a Array<T, A>
+impl<'a, T, A, K> IntoIterator for &'a Array<T, A, K>
 where
     T: Uniform,
     A: AsMut<[Element<T>]> + AsRef<[Element<T>]>,
@@ -93,7 +129,7 @@ where
     }
 }
 
-impl<'a, T, A> IntoIterator for &'a mut Array<T, A>
+impl<'a, T, A, K> IntoIterator for &'a mut Array<T, A, K>
 where
     T: Uniform,
     A: AsMut<[Element<T>]> + AsRef<[Element<T>]>,
@@ -192,7 +228,7 @@ where
     }
 }
 
-impl<T, U, const N: usize> From<[T; N]> for Array<U, [U; N]>
+impl<T, U, K, const N: usize> From<[T; N]> for Array<U, [U; N], K>
 where
     T: Into<U>,
 {
@@ -201,7 +237,7 @@ where
     }
 }
 
-impl<T, U, const N: usize> From<[T; N]> for Array<U, [Element<U>; N]>
+impl<T, U, K, const N: usize> From<[T; N]> for Array<U, [Element<U>; N], K>
 where
     T: Into<U>,
     U: Uniform,
@@ -212,12 +248,24 @@ where
     }
 }
 
+impl<T, U, K, const N: usize> From<Array<U, [Element<U>; N], K>> for [T; N]
+where
+    U: Uniform + Into<T>,
+{
+    fn from(values: Array<U, [Element<U>; N], K>) -> Self {
+        MapArray::map_array(values.0, |elem: Element<U>| elem.0.into())
+    }
+}
+
 impl<T, const N: usize> Uniform for [T; N]
 where
     T: Uniform,
 {
     type Align = Align16;
     type Std140 = Array<T::Std140, [Element<T::Std140>; N]>;
+    const TYPE_INFO: TypeInfo = TypeInfo::Array(&T::TYPE_INFO, N);
+    const IS_STD140: IsStd140<Self> =
+        IsStd140::new(T::IS_STD140.0 && size_of::<T>() == size_of::<Element<T>>());
 
     fn std140(&self) -> Array<T::Std140, [Element<T::Std140>; N]> {
         use std::ptr::write;
@@ -234,16 +282,28 @@ where
             Array(result.assume_init(), PhantomData)
         }
     }
+
+    fn write_std140(&self, out: &mut [u8]) {
+        write_elements(self.iter(), &mut out[..Self::STD140_SIZE]);
+    }
 }
 
-impl<T, const N: usize> Uniform for Array<T, [Element<T>; N]>
+impl<T, K, const N: usize> Uniform for Array<T, [Element<T>; N], K>
 where
     T: Uniform,
+    K: ArrayKind,
 {
     type Align = Align16;
-    type Std140 = Array<T::Std140, [Element<T::Std140>; N]>;
-
-    fn std140(&self) -> Array<T::Std140, [Element<T::Std140>; N]> {
+    type Std140 = Array<T::Std140, [Element<T::Std140>; N], K>;
+    const TYPE_INFO: TypeInfo = if K::MATRIX {
+        TypeInfo::array_or_matrix(&T::TYPE_INFO, N)
+    } else {
+        TypeInfo::Array(&T::TYPE_INFO, N)
+    };
+    const IS_STD140: IsStd140<Self> =
+        IsStd140::new(T::IS_STD140.0 && size_of::<T>() == size_of::<Element<T>>());
+
+    fn std140(&self) -> Array<T::Std140, [Element<T::Std140>; N], K> {
         use std::ptr::write;
         unsafe {
             // All elements of `result` is written.
@@ -258,9 +318,30 @@ where
             Array(result.assume_init(), PhantomData)
         }
     }
+
+    fn write_std140(&self, out: &mut [u8]) {
+        write_elements(self.iter(), &mut out[..Self::STD140_SIZE]);
+    }
+}
+
+unsafe impl<T, K, const N: usize> Std140 for Array<T, [Element<T>; N], K>
+where
+    T: Std140,
+    K: ArrayKind,
+{
 }
 
-unsafe impl<T, const N: usize> Std140 for Array<T, [Element<T>; N]> where T: Std140 {}
+/// Write elements one by one with array stride of `std140`, zeroing padding between them.
+fn write_elements<'a, T>(items: impl Iterator<Item = &'a T>, out: &mut [u8])
+where
+    T: Uniform + 'a,
+{
+    let stride = size_of::<Element<T::Std140>>();
+    for (item, out) in items.zip(out.chunks_mut(stride)) {
+        item.write_std140(out);
+        out[T::STD140_SIZE..].fill(0);
+    }
+}
 
 #[test]
 fn test_array() {
@@ -311,3 +392,20 @@ fn test_array() {
     ]
     .std140();
 }
+
+#[test]
+fn test_array_type_info() {
+    use crate::{imat2, mat4, vec4};
+
+    type Std140Array = <[vec4; 4] as Uniform>::Std140;
+    assert!(matches!(Std140Array::TYPE_INFO, TypeInfo::Array(_, 4)));
+    assert!(matches!(
+        mat4::TYPE_INFO,
+        TypeInfo::Matrix {
+            columns: 4,
+            rows: 4,
+            ..
+        }
+    ));
+    assert!(matches!(imat2::TYPE_INFO, TypeInfo::Array(_, 2)));
+}
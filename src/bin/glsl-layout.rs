@@ -0,0 +1,284 @@
+//! Print layouts of uniform and buffer blocks declared in GLSL file.
+
+use glsl_layout::build::{declarations, Declaration};
+use glsl_layout::glsl::type_name;
+use glsl_layout::layout::{member_layouts, MemberLayout, Scalar, Std140, Std430};
+use glsl_layout::reflect::TypeInfo;
+use std::process::exit;
+
+const USAGE: &str = "Usage: glsl-layout [--layout std140|std430|scalar] [--json] <file>
+
+Prints offsets, sizes, strides and padding of members of every uniform and buffer block
+declared in GLSL file and files it includes. Blocks are laid out as declared unless
+`--layout` is given. Runtime-sized arrays are shown with offset and stride of their elements.";
+
+struct Options {
+    layout: Option<String>,
+    json: bool,
+    file: String,
+}
+
+/// Laid out block.
+struct Block {
+    declaration: Declaration,
+    layout: String,
+    /// Size of the block, without runtime-sized array.
+    size: usize,
+    members: Vec<Member>,
+}
+
+struct Member {
+    layout: MemberLayout,
+    ty: String,
+    /// Size and padding are unknown for runtime-sized array.
+    runtime_sized: bool,
+}
+
+fn main() {
+    let options = parse_args().unwrap_or_else(|message| {
+        eprintln!("error: {}\n\n{}", message, USAGE);
+        exit(2)
+    });
+
+    let blocks = blocks(&options).unwrap_or_else(|message| {
+        eprintln!("error: {}", message);
+        exit(1)
+    });
+
+    if options.json {
+        print_json(&blocks);
+    } else {
+        print_tables(&blocks);
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut layout = None;
+    let mut json = false;
+    let mut file = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                exit(0)
+            }
+            "--json" => json = true,
+            "--layout" => {
+                let value = args.next().ok_or("`--layout` requires a value")?;
+                if !["std140", "std430", "scalar"].contains(&value.as_str()) {
+                    return Err(format!("Unknown layout `{}`", value));
+                }
+                layout = Some(value);
+            }
+            _ if arg.starts_with('-') => return Err(format!("Unknown option `{}`", arg)),
+            _ if file.is_none() => file = Some(arg),
+            _ => return Err(format!("Unexpected argument `{}`", arg)),
+        }
+    }
+
+    Ok(Options {
+        layout,
+        json,
+        file: file.ok_or("GLSL file is not specified")?,
+    })
+}
+
+fn blocks(options: &Options) -> Result<Vec<Block>, String> {
+    let declarations = declarations(&options.file).map_err(|err| err.to_string())?;
+
+    let mut blocks = Vec::new();
+    for declaration in declarations {
+        let packing = match declaration.packing {
+            Some(packing) => packing,
+            None => continue,
+        };
+        let layout = options.layout.as_deref().unwrap_or(packing);
+        let layouts = match layout {
+            "std140" => member_layouts::<Std140>(declaration.ty),
+            "std430" => member_layouts::<Std430>(declaration.ty),
+            "scalar" => member_layouts::<Scalar>(declaration.ty),
+            _ => {
+                return Err(format!(
+                    "Layout `{}` of block `{}` is not supported",
+                    layout, declaration.name
+                ))
+            }
+        };
+
+        // Runtime-sized array is the last member of the block itself.
+        let last = layouts
+            .iter()
+            .rposition(|member| !member.path.contains('.'));
+        let members = layouts
+            .into_iter()
+            .enumerate()
+            .map(|(index, layout)| {
+                let runtime_sized = declaration.runtime_sized && Some(index) == last;
+                let ty = match *layout.ty {
+                    TypeInfo::Array(elem, _) if runtime_sized => {
+                        let name = type_name(elem);
+                        let at = name.find('[').unwrap_or(name.len());
+                        format!("{}[]{}", &name[..at], &name[at..])
+                    }
+                    ref ty => type_name(ty),
+                };
+                Member {
+                    layout,
+                    ty,
+                    runtime_sized,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let size = members
+            .iter()
+            .map(|member| match member.runtime_sized {
+                true => member.layout.offset,
+                false => member.layout.offset + member.layout.size + member.layout.padding,
+            })
+            .max()
+            .unwrap_or(0);
+
+        blocks.push(Block {
+            declaration,
+            layout: layout.to_owned(),
+            size,
+            members,
+        });
+    }
+    Ok(blocks)
+}
+
+fn optional(value: Option<usize>, none: &str) -> String {
+    value.map_or_else(|| none.to_owned(), |value| value.to_string())
+}
+
+fn print_tables(blocks: &[Block]) {
+    for (index, block) in blocks.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        println!(
+            "{} {} ({}, {} bytes)",
+            block.declaration.kind, block.declaration.name, block.layout, block.size
+        );
+
+        let header = [
+            "path",
+            "type",
+            "offset",
+            "size",
+            "array stride",
+            "matrix stride",
+            "padding",
+        ];
+        let rows = block
+            .members
+            .iter()
+            .map(|member| {
+                let layout = &member.layout;
+                let known = |value: usize| match member.runtime_sized {
+                    true => "-".to_owned(),
+                    false => value.to_string(),
+                };
+                [
+                    layout.path.clone(),
+                    member.ty.clone(),
+                    layout.offset.to_string(),
+                    known(layout.size),
+                    optional(layout.array_stride, "-"),
+                    optional(layout.matrix_stride, "-"),
+                    known(layout.padding),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+
+        let print_row = |cells: [&str; 7]| {
+            let mut line = String::from(" ");
+            for (column, (cell, width)) in cells.iter().zip(widths).enumerate() {
+                // Names are aligned to the left, numbers to the right.
+                if column < 2 {
+                    line += &format!(" {:<width$}", cell, width = width);
+                } else {
+                    line += &format!(" {:>width$}", cell, width = width);
+                }
+            }
+            println!("{}", line.trim_end());
+        };
+        print_row(header);
+        for row in &rows {
+            print_row(row.each_ref().map(String::as_str));
+        }
+    }
+}
+
+fn print_json(blocks: &[Block]) {
+    let mut json = String::from("[");
+    for (index, block) in blocks.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+        json += &format!(
+            "\n  {{\n    \"kind\": {},\n    \"name\": {},\n    \"layout\": {},\n    \"size\": {},\n    \"runtime_sized\": {},\n    \"members\": [",
+            json_string(block.declaration.kind),
+            json_string(block.declaration.name),
+            json_string(&block.layout),
+            block.size,
+            block.declaration.runtime_sized
+        );
+        for (index, member) in block.members.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let layout = &member.layout;
+            let known = |value: usize| match member.runtime_sized {
+                true => "null".to_owned(),
+                false => value.to_string(),
+            };
+            json += &format!(
+                "\n      {{\"path\": {}, \"type\": {}, \"offset\": {}, \"size\": {}, \"array_stride\": {}, \"matrix_stride\": {}, \"padding\": {}}}",
+                json_string(&layout.path),
+                json_string(&member.ty),
+                layout.offset,
+                known(layout.size),
+                optional(layout.array_stride, "null"),
+                optional(layout.matrix_stride, "null"),
+                known(layout.padding)
+            );
+        }
+        json += "\n    ]\n  }";
+    }
+    json += "\n]";
+    println!("{}", json);
+}
+
+/// Quoted JSON string with special characters escaped.
+fn json_string(value: &str) -> String {
+    let mut json = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => json += "\\\"",
+            '\\' => json += "\\\\",
+            '\n' => json += "\\n",
+            c if c.is_control() => json += &format!("\\u{:04x}", c as u32),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[test]
+fn test_json_string() {
+    assert_eq!(json_string("lights[0].position"), "\"lights[0].position\"");
+    assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+}
@@ -0,0 +1,465 @@
+use crate::align::Align16;
+use crate::array::Element;
+use crate::layout::{Layout, Std140 as Std140Rules};
+use crate::reflect::{MemberInfo, StructInfo, StructName, TypeInfo};
+use crate::scalar::uint;
+use crate::uniform::{Std140, Uniform};
+use std::{
+    convert::TryFrom,
+    fmt,
+    mem::{size_of, MaybeUninit},
+    slice::from_raw_parts_mut,
+};
+
+/// Error returned when `BoundedArray` has no space for more elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityError {
+    /// Maximal number of elements.
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Array can't hold more than {} elements", self.capacity)
+    }
+}
+
+impl std::error::Error for CapacityError {}
+
+/// Array of up to `N` elements with number of live elements.
+/// Laid out as `uint count` followed by `T items[N]`, elements after `count` are zeroed.
+///
+/// On its own it is declared as structure with members `count` and `items`,
+/// named after its parameters, i.e. `BoundedArray_Light_8`.
+/// Members of derived structures can be flattened instead with `#[glsl(count = "name")]`
+/// attribute that gives name to the count, which is placed before the array,
+/// or after it with `#[glsl(count = "name", count_after)]`.
+///
+/// ```
+/// # use glsl_layout::*;
+/// #[derive(Clone, Copy, Default, Uniform)]
+/// struct Light {
+///     position: vec3,
+///     intensity: float,
+/// }
+///
+/// #[derive(Clone, Copy, Default, Uniform)]
+/// struct Lights {
+///     #[glsl(count = "light_count")]
+///     lights: BoundedArray<Light, 8>,
+/// }
+/// ```
+pub struct BoundedArray<T, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    len: usize,
+}
+
+impl<T, const N: usize> Clone for BoundedArray<T, N>
+where
+    T: Copy,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const N: usize> Copy for BoundedArray<T, N> where T: Copy {}
+
+impl<T, const N: usize> BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    /// Maximal number of elements.
+    pub const CAPACITY: usize = N;
+
+    /// Create empty array.
+    pub fn new() -> Self {
+        BoundedArray {
+            items: [MaybeUninit::uninit(); N],
+            len: 0,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Append element.
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.len == N {
+            return Err(CapacityError { capacity: N });
+        }
+        self.items[self.len] = MaybeUninit::new(value);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Remove all elements.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Elements as slice.
+    pub fn as_slice(&self) -> &[T] {
+        // First `len` elements are initialized.
+        unsafe { &*(&self.items[..self.len] as *const [MaybeUninit<T>] as *const [T]) }
+    }
+
+    /// Elements as mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // First `len` elements are initialized.
+        unsafe { &mut *(&mut self.items[..self.len] as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+}
+
+impl<T, const N: usize> Default for BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    fn default() -> Self {
+        BoundedArray::new()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for BoundedArray<T, N>
+where
+    T: Uniform + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> TryFrom<&[T]> for BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    type Error = CapacityError;
+
+    fn try_from(values: &[T]) -> Result<Self, CapacityError> {
+        let mut array = BoundedArray::new();
+        for &value in values {
+            array.push(value)?;
+        }
+        Ok(array)
+    }
+}
+
+/// Access to count and array of `BoundedArray` for `derive(Uniform)`.
+#[doc(hidden)]
+pub trait Bounded {
+    type Array: Uniform;
+
+    fn count(&self) -> uint;
+
+    fn array_std140(&self) -> <Self::Array as Uniform>::Std140;
+
+    /// Write elements in `std140` layout, zeroing padding and unused slots.
+    fn write_array(&self, out: &mut [u8]);
+}
+
+impl<T, const N: usize> Bounded for BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    type Array = [T; N];
+
+    fn count(&self) -> uint {
+        self.len as uint
+    }
+
+    fn array_std140(&self) -> <[T; N] as Uniform>::Std140 {
+        let mut array = MaybeUninit::<<[T; N] as Uniform>::Std140>::zeroed();
+        let stride = size_of::<Element<T::Std140>>();
+        // `Std140` types have no padding bytes.
+        let raw = unsafe {
+            from_raw_parts_mut(
+                array.as_mut_ptr() as *mut u8,
+                size_of::<<[T; N] as Uniform>::Std140>(),
+            )
+        };
+        for (index, item) in self.as_slice().iter().enumerate() {
+            item.write_std140(&mut raw[index * stride..]);
+        }
+        // Any bit pattern is valid for `Std140` types.
+        unsafe { array.assume_init() }
+    }
+
+    fn write_array(&self, out: &mut [u8]) {
+        let out = &mut out[..<[T; N] as Uniform>::STD140_SIZE];
+        let stride = <T as Layout<Std140Rules>>::ARRAY_STRIDE;
+        for (item, out) in self.as_slice().iter().zip(out.chunks_mut(stride)) {
+            item.write_std140(out);
+            out[T::STD140_SIZE..].fill(0);
+        }
+        out[self.len * stride..].fill(0);
+    }
+}
+
+/// `std140` representation of `BoundedArray`.
+#[repr(C, align(16))]
+pub struct LayoutStd140BoundedArray<T: Uniform, const N: usize> {
+    count: uint,
+    _pad: [uint; 3],
+    items: <[T; N] as Uniform>::Std140,
+}
+
+impl<T, const N: usize> Clone for LayoutStd140BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const N: usize> Copy for LayoutStd140BoundedArray<T, N> where T: Uniform {}
+
+impl<T, const N: usize> Default for LayoutStd140BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    fn default() -> Self {
+        BoundedArray::<T, N>::new().std140()
+    }
+}
+
+impl<T, const N: usize> fmt::Debug for LayoutStd140BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutStd140BoundedArray")
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+// Count is padded explicitly and the array has no padding bytes.
+unsafe impl<T, const N: usize> Std140 for LayoutStd140BoundedArray<T, N> where T: Uniform {}
+
+impl<T, const N: usize> Uniform for LayoutStd140BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    type Align = Align16;
+    type Std140 = Self;
+    const TYPE_INFO: TypeInfo = <BoundedArray<T, N> as Uniform>::TYPE_INFO;
+
+    fn std140(&self) -> Self {
+        *self
+    }
+
+    fn write_std140(&self, out: &mut [u8]) {
+        let out = &mut out[..Self::STD140_SIZE];
+        self.count.write_std140(out);
+        out[4..16].fill(0);
+        self.items.write_std140(&mut out[16..]);
+    }
+}
+
+impl<T, const N: usize> BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    const NAME: StructName = StructName::new("BoundedArray")
+        .with_type(&T::TYPE_INFO)
+        .with_len(N);
+}
+
+impl<T, const N: usize> Uniform for BoundedArray<T, N>
+where
+    T: Uniform,
+{
+    type Align = Align16;
+    type Std140 = LayoutStd140BoundedArray<T, N>;
+    const TYPE_INFO: TypeInfo = TypeInfo::Struct(&StructInfo::new(
+        Self::NAME.as_str(),
+        &[
+            MemberInfo::new("count", &<uint as Uniform>::TYPE_INFO),
+            MemberInfo::new("items", &<[T; N] as Uniform>::TYPE_INFO),
+        ],
+    ));
+
+    fn std140(&self) -> LayoutStd140BoundedArray<T, N> {
+        LayoutStd140BoundedArray {
+            count: self.count(),
+            _pad: [0; 3],
+            items: self.array_std140(),
+        }
+    }
+
+    fn write_std140(&self, out: &mut [u8]) {
+        let out = &mut out[..Self::STD140_SIZE];
+        self.count().write_std140(out);
+        out[4..16].fill(0);
+        self.write_array(&mut out[16..]);
+    }
+}
+
+#[test]
+fn test_bounded_array() {
+    use crate as glsl_layout;
+    use crate::layout::{Layout, Std430};
+    use crate::{float, glsl, vec3};
+
+    #[derive(Clone, Copy, Debug, Default, Uniform)]
+    struct Light {
+        position: vec3,
+        intensity: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Lights {
+        ambient: float,
+        #[glsl(count = "light_count")]
+        lights: BoundedArray<Light, 4>,
+        #[glsl(count = "weight_count", count_after)]
+        weights: BoundedArray<float, 3>,
+    }
+
+    let light = |x: f32| Light {
+        position: [x, 0.0, 0.0].into(),
+        intensity: x,
+    };
+    let mut lights = BoundedArray::<Light, 4>::try_from(&[light(1.0), light(2.0)][..]).unwrap();
+    assert_eq!(lights.len(), 2);
+    lights.push(light(3.0)).unwrap();
+    lights.push(light(4.0)).unwrap();
+    assert_eq!(lights.push(light(5.0)), Err(CapacityError { capacity: 4 }));
+    assert!(BoundedArray::<float, 1>::try_from(&[1.0, 2.0][..]).is_err());
+
+    let value = Lights {
+        ambient: 0.5,
+        lights,
+        weights: BoundedArray::try_from(&[6.0][..]).unwrap(),
+    };
+
+    // ambient: 0, light_count: 4, lights: 16, weights: 80, weight_count: 128.
+    let std140 = value.std140();
+    let raw = std140.as_raw();
+    assert_eq!(raw.len(), 144);
+    assert_eq!(&raw[4..8], &4u32.to_ne_bytes());
+    assert_eq!(&raw[60..64], &3.0f32.to_ne_bytes());
+    assert_eq!(&raw[80..84], &6.0f32.to_ne_bytes());
+    assert!(raw[96..128].iter().all(|&byte| byte == 0));
+    assert_eq!(&raw[128..132], &1u32.to_ne_bytes());
+
+    let mut written = vec![0xff; Lights::STD140_SIZE];
+    value.write_std140(&mut written);
+    let mut significant = vec![false; written.len()];
+    crate::layout::fields::<crate::layout::Std140>(&Lights::TYPE_INFO, 0, &mut |range| {
+        assert_eq!(written[range.clone()], raw[range.clone()]);
+        significant[range].fill(true);
+    });
+    assert!(written
+        .iter()
+        .zip(&significant)
+        .all(|(&byte, &significant)| significant || byte == 0));
+
+    // ambient: 0, light_count: 4, lights: 16, weights: 80, weight_count: 92.
+    assert_eq!(<Lights as Layout<Std430>>::SIZE, 96);
+
+    assert_eq!(
+        glsl::uniform_block::<Lights>("Lights", None),
+        "struct Light
+{
+    vec3 position;
+    float intensity;
+};
+
+layout(std140) uniform Lights
+{
+    float ambient;
+    uint light_count;
+    Light lights[4];
+    float weights[3];
+    uint weight_count;
+};
+"
+    );
+
+    // Padding of elements and unused slots is zeroed, flattened or not.
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Spot {
+        angle: float,
+        direction: vec3,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Spots {
+        #[glsl(count = "spot_count")]
+        spots: BoundedArray<Spot, 3>,
+    }
+
+    let spot = Spot {
+        angle: 1.0,
+        direction: [2.0, 3.0, 4.0].into(),
+    };
+    let spots = Spots {
+        spots: BoundedArray::try_from(&[spot][..]).unwrap(),
+    };
+    // spot_count: 0, spots: 16 with stride 32, angle at 0 and direction at 16.
+    let mut expected = vec![0; 112];
+    expected[0..4].copy_from_slice(&1u32.to_ne_bytes());
+    expected[16..20].copy_from_slice(&1.0f32.to_ne_bytes());
+    for (index, x) in [2.0f32, 3.0, 4.0].iter().enumerate() {
+        expected[32 + index * 4..36 + index * 4].copy_from_slice(&x.to_ne_bytes());
+    }
+
+    let mut written = vec![0xff; Spots::STD140_SIZE];
+    spots.write_std140(&mut written);
+    assert_eq!(written, expected);
+    let mut written = vec![0xff; Spots::STD140_SIZE];
+    spots.std140().write_std140(&mut written);
+    assert_eq!(written, expected);
+    let mut written = vec![0xff; 112];
+    spots.spots.write_std140(&mut written);
+    assert_eq!(written, expected);
+
+    // Instantiations that are not flattened are declared as distinct structures.
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Arrays {
+        lights: BoundedArray<Light, 4>,
+        weights: BoundedArray<float, 8>,
+    }
+
+    assert_eq!(
+        glsl::uniform_block::<Arrays>("Arrays", None),
+        "struct Light
+{
+    vec3 position;
+    float intensity;
+};
+
+struct BoundedArray_Light_4
+{
+    uint count;
+    Light items[4];
+};
+
+struct BoundedArray_float_8
+{
+    uint count;
+    float items[8];
+};
+
+layout(std140) uniform Arrays
+{
+    BoundedArray_Light_4 lights;
+    BoundedArray_float_8 weights;
+};
+"
+    );
+
+    lights.clear();
+    let std140 = lights.std140();
+    assert_eq!(std140.as_raw().len(), 80);
+    assert!(std140.as_raw().iter().all(|&byte| byte == 0));
+}
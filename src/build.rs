@@ -0,0 +1,565 @@
+//! Generation of Rust modules with `derive(Uniform)` structures from GLSL sources.
+//! Intended to be used from build scripts.
+//!
+//! ```no_run
+//! // build.rs
+//! glsl_layout::build::Builder::new()
+//!     .dir("shaders")
+//!     .write("shaders.rs")
+//!     .unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/shaders.rs
+//! include!(concat!(env!("OUT_DIR"), "/shaders.rs"));
+//! ```
+
+use crate::reflect::{MemberInfo, ScalarType, StructInfo, TypeInfo};
+use glsl_layout_parser::{LoadError, Member, Module, Scalar, Storage, Type};
+use std::{
+    collections::HashMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Extensions of files that `Builder::dir` picks up.
+const EXTENSIONS: &[&str] = &["glsl", "vert", "frag", "comp", "geom", "tesc", "tese"];
+
+/// Rust keywords, GLSL names that match them become raw identifiers.
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "extern", "false", "fn", "for",
+    "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+    "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "async", "await", "dyn", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen",
+];
+
+/// Error of module generation.
+#[derive(Debug)]
+pub enum BuildError {
+    /// File can't be read or written.
+    Io {
+        /// Path of the file.
+        path: PathBuf,
+        /// Underlying error.
+        error: io::Error,
+    },
+
+    /// Declaration is invalid or can't be represented in Rust.
+    Glsl {
+        /// Path of the file.
+        path: PathBuf,
+        /// Line of the declaration.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            BuildError::Glsl {
+                path,
+                line,
+                message,
+            } => write!(f, "{}:{}: {}", path.display(), line, message),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::Io { error, .. } => Some(error),
+            BuildError::Glsl { .. } => None,
+        }
+    }
+}
+
+/// Generator of Rust module with structures for every structure and block declared in GLSL files.
+/// Files included with `#include "path"` are resolved relative to the including file.
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    files: Vec<PathBuf>,
+    dirs: Vec<PathBuf>,
+}
+
+impl Builder {
+    /// Create builder without files.
+    pub fn new() -> Self {
+        Builder::default()
+    }
+
+    /// Add GLSL file.
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.files.push(path.as_ref().to_owned());
+        self
+    }
+
+    /// Add all GLSL files in the directory and its subdirectories.
+    /// Files are recognized by extensions `.glsl`, `.vert`, `.frag`, `.comp`, `.geom`, `.tesc` and `.tese`.
+    pub fn dir(mut self, path: impl AsRef<Path>) -> Self {
+        self.dirs.push(path.as_ref().to_owned());
+        self
+    }
+
+    /// Generate source of the module.
+    pub fn generate(&self) -> Result<String, BuildError> {
+        self.run().map(|(source, _)| source)
+    }
+
+    /// Write the module into file `name` in `OUT_DIR`
+    /// and tell cargo to run build script again when any of the GLSL files change.
+    ///
+    /// # Panics
+    ///
+    /// If `OUT_DIR` environment variable is not set.
+    pub fn write(&self, name: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+        let (source, read) = self.run()?;
+        for path in self.dirs.iter().chain(&read) {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+
+        let out_dir = std::env::var_os("OUT_DIR").expect("`OUT_DIR` must be set by cargo");
+        let path = Path::new(&out_dir).join(name);
+        fs::write(&path, source).map_err(|error| BuildError::Io {
+            path: path.clone(),
+            error,
+        })?;
+        Ok(path)
+    }
+
+    /// Generate source of the module and list files that were read.
+    fn run(&self) -> Result<(String, Vec<PathBuf>), BuildError> {
+        let mut files = self.files.clone();
+        for dir in &self.dirs {
+            scan(dir, &mut files)?;
+        }
+
+        let mut sources = Sources::default();
+        for file in &files {
+            sources.load(file)?;
+        }
+        let source = sources.generate()?;
+        Ok((source, sources.read))
+    }
+}
+
+/// Find GLSL files in the directory recursively.
+fn scan(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), BuildError> {
+    let io = |error| BuildError::Io {
+        path: dir.to_owned(),
+        error,
+    };
+    let mut entries = fs::read_dir(dir)
+        .map_err(io)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io)?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            scan(&path, files)?;
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| EXTENSIONS.contains(&ext))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Declarations of all loaded files.
+#[derive(Default)]
+struct Sources {
+    module: Module,
+    /// Files in order they were parsed.
+    read: Vec<PathBuf>,
+    /// Canonical paths of parsed files.
+    canonical: Vec<PathBuf>,
+    /// Index of file for every structure.
+    struct_files: Vec<usize>,
+    /// Index of file for every block.
+    block_files: Vec<usize>,
+}
+
+impl Sources {
+    /// Parse the file after files it includes, unless it was parsed already.
+    fn load(&mut self, path: &Path) -> Result<(), BuildError> {
+        let Sources {
+            module,
+            read,
+            canonical,
+            struct_files,
+            block_files,
+        } = self;
+        module
+            .load(path, canonical, &mut |module, path| {
+                let index = read.len();
+                read.push(path.to_owned());
+                struct_files.resize(module.structs.len(), index);
+                block_files.resize(module.blocks.len(), index);
+            })
+            .map_err(|error| match error {
+                LoadError::Io { path, error } => BuildError::Io { path, error },
+                LoadError::Parse { path, error } => BuildError::Glsl {
+                    path,
+                    line: error.line,
+                    message: error.message,
+                },
+            })
+    }
+
+    fn generate(&self) -> Result<String, BuildError> {
+        let mut source = String::from("// Generated by glsl-layout from GLSL sources.\n");
+
+        let mut constants: Vec<&(String, usize)> = Vec::new();
+        for constant in self.module.constants.iter().rev() {
+            if constants.iter().all(|(name, _)| *name != constant.0) {
+                constants.insert(0, constant);
+            }
+        }
+        if !constants.is_empty() {
+            source.push('\n');
+        }
+        for (name, value) in constants {
+            source += &format!("pub const {}: usize = {};\n", ident(name), value);
+        }
+
+        let structs = self.module.structs.iter().zip(&self.struct_files);
+        let blocks = self.module.blocks.iter().zip(&self.block_files);
+        let declarations = structs
+            .map(|(s, &file)| ("Structure", &s.name, s.line, &s.members, None, file))
+            .chain(blocks.map(|(b, &file)| {
+                let kind = match b.storage {
+                    Storage::Uniform => "Uniform block",
+                    Storage::Buffer => "Buffer block",
+                };
+                (kind, &b.name, b.line, &b.members, Some(b.packing()), file)
+            }));
+
+        let mut generated: Vec<(&String, &Vec<Member>)> = Vec::new();
+        for (kind, name, line, members, packing, file) in declarations {
+            let path = &self.read[file];
+            let error = |message: String| BuildError::Glsl {
+                path: path.clone(),
+                line,
+                message,
+            };
+
+            match generated.iter().find(|(other, _)| *other == name) {
+                Some((_, other)) if *other == members => continue,
+                Some(_) => return Err(error(format!("`{}` is declared differently", name))),
+                None => generated.push((name, members)),
+            }
+
+            source.push('\n');
+            source += &format!(
+                "/// {} `{}` declared at `{}:{}`.\n",
+                kind,
+                name,
+                path.display(),
+                line
+            );
+
+            // Trailing runtime-sized array of a buffer block is left out.
+            let (members, unsized_member) = match members.split_last() {
+                Some((last, rest)) if last.dims.first() == Some(&None) => (rest, Some(last)),
+                _ => (&members[..], None),
+            };
+            if let Some(member) = unsized_member {
+                source += &format!(
+                    "///\n/// Runtime-sized array `{}` is not included, use `UnsizedBlock` for it.\n",
+                    member.name
+                );
+            }
+
+            source += if self.module.derives_default(members) {
+                "#[derive(Clone, Copy, Debug, Default, glsl_layout::Uniform)]\n"
+            } else {
+                "#[derive(Clone, Copy, Debug, glsl_layout::Uniform)]\n"
+            };
+            match packing {
+                None | Some("std140") => {}
+                Some(packing @ ("std430" | "scalar")) => {
+                    source += &format!("#[glsl(layouts({}))]\n", packing)
+                }
+                Some(packing) => {
+                    return Err(error(format!("Layout `{}` is not supported", packing)))
+                }
+            }
+
+            source += &format!("pub struct {} {{\n", ident(name));
+            for member in members {
+                let ty = rust_type(&member.ty, &member.dims)
+                    .map_err(|message| error(format!("`{}`: {}", member.name, message)))?;
+                source += &format!("    pub {}: {},\n", ident(&member.name), ty);
+            }
+            source += "}\n";
+        }
+        Ok(source)
+    }
+}
+
+/// Structure or block declared in GLSL source.
+#[derive(Clone, Copy, Debug)]
+pub struct Declaration {
+    /// `struct`, `uniform` or `buffer`.
+    pub kind: &'static str,
+
+    /// Name of the structure or block.
+    pub name: &'static str,
+
+    /// Layout qualifier of the block, `std140` or `std430` by default.
+    /// `None` for structures.
+    pub packing: Option<&'static str>,
+
+    /// Members described as structure.
+    pub ty: &'static TypeInfo,
+
+    /// Last member is runtime-sized array.
+    /// It is described as array of one element.
+    pub runtime_sized: bool,
+}
+
+/// Parse structures and blocks declared in GLSL file and files it includes.
+/// Descriptions are leaked to get `'static` lifetime as `TypeInfo` requires,
+/// thus it is meant for tools and build scripts.
+pub fn declarations(path: impl AsRef<Path>) -> Result<Vec<Declaration>, BuildError> {
+    let mut sources = Sources::default();
+    sources.load(path.as_ref())?;
+    let module = &sources.module;
+
+    let mut cache = HashMap::new();
+    let mut declarations = module
+        .structs
+        .iter()
+        .map(|s| Declaration {
+            kind: "struct",
+            name: leak(&s.name),
+            packing: None,
+            ty: leak_type(TypeInfo::Struct(struct_info(module, &s.name, &mut cache))),
+            runtime_sized: false,
+        })
+        .collect::<Vec<_>>();
+
+    declarations.extend(module.blocks.iter().map(|b| {
+        Declaration {
+            kind: match b.storage {
+                Storage::Uniform => "uniform",
+                Storage::Buffer => "buffer",
+            },
+            name: leak(&b.name),
+            packing: Some(leak(b.packing())),
+            ty: leak_type(TypeInfo::Struct(Box::leak(Box::new(StructInfo::new(
+                leak(&b.name),
+                member_infos(module, &b.members, &mut cache),
+            ))))),
+            runtime_sized: b
+                .members
+                .last()
+                .is_some_and(|member| member.dims.first() == Some(&None)),
+        }
+    }));
+    Ok(declarations)
+}
+
+fn leak(value: &str) -> &'static str {
+    Box::leak(value.to_owned().into_boxed_str())
+}
+
+fn leak_type(ty: TypeInfo) -> &'static TypeInfo {
+    Box::leak(Box::new(ty))
+}
+
+fn struct_info(
+    module: &Module,
+    name: &str,
+    cache: &mut HashMap<String, &'static StructInfo>,
+) -> &'static StructInfo {
+    if let Some(info) = cache.get(name) {
+        return info;
+    }
+    let members = &module.find_struct(name).unwrap().members;
+    let info = Box::leak(Box::new(StructInfo::new(
+        leak(name),
+        member_infos(module, members, cache),
+    )));
+    cache.insert(name.to_owned(), info);
+    info
+}
+
+fn member_infos(
+    module: &Module,
+    members: &[Member],
+    cache: &mut HashMap<String, &'static StructInfo>,
+) -> &'static [MemberInfo] {
+    let infos = members
+        .iter()
+        .map(|member| {
+            let mut ty = match &member.ty {
+                Type::Scalar(scalar) => TypeInfo::Scalar(scalar_type(*scalar)),
+                Type::Vector(scalar, len) => TypeInfo::Vector(scalar_type(*scalar), *len),
+                &Type::Matrix {
+                    scalar,
+                    columns,
+                    rows,
+                } => TypeInfo::Matrix {
+                    scalar: scalar_type(scalar),
+                    columns,
+                    rows,
+                },
+                Type::Struct(name) => TypeInfo::Struct(struct_info(module, name, cache)),
+            };
+            for len in member.dims.iter().rev() {
+                ty = TypeInfo::Array(leak_type(ty), len.unwrap_or(1));
+            }
+            MemberInfo::new(leak(&member.name), leak_type(ty))
+        })
+        .collect::<Vec<_>>();
+    infos.leak()
+}
+
+fn scalar_type(scalar: Scalar) -> ScalarType {
+    match scalar {
+        Scalar::Bool => ScalarType::Bool,
+        Scalar::Int => ScalarType::Int,
+        Scalar::Uint => ScalarType::Uint,
+        Scalar::Float => ScalarType::Float,
+        Scalar::Double => ScalarType::Double,
+    }
+}
+
+/// Rust type for GLSL type with array dimensions.
+fn rust_type(ty: &Type, dims: &[Option<usize>]) -> Result<String, String> {
+    if let Some((&len, dims)) = dims.split_first() {
+        let len = len.ok_or_else(|| "Runtime-sized array must be the last member".to_owned())?;
+        return Ok(format!("[{}; {}]", rust_type(ty, dims)?, len));
+    }
+
+    Ok(match ty {
+        Type::Scalar(Scalar::Bool) => "glsl_layout::boolean".to_owned(),
+        Type::Struct(name) => ident(name),
+        ty => format!("glsl_layout::{}", ty.name()),
+    })
+}
+
+/// Identifier for GLSL name, raw if it is a Rust keyword.
+fn ident(name: &str) -> String {
+    if KEYWORDS.contains(&name) {
+        format!("r#{}", name)
+    } else {
+        name.to_owned()
+    }
+}
+
+#[test]
+fn test_build() {
+    let source = Builder::new()
+        .file("tests/test_include.glsl")
+        .file("tests/test.glsl")
+        .generate()
+        .unwrap();
+
+    // `test.glsl` is parsed once, before `test_include.glsl` that includes it.
+    assert_eq!(
+        source,
+        "// Generated by glsl-layout from GLSL sources.
+
+pub const MAX_LIGHTS: usize = 4;
+
+/// Structure `Light` declared at `tests/test.glsl:5`.
+#[derive(Clone, Copy, Debug, Default, glsl_layout::Uniform)]
+pub struct Light {
+    pub position: glsl_layout::vec3,
+    pub intensity: glsl_layout::float,
+}
+
+/// Uniform block `Camera` declared at `tests/test.glsl:10`.
+#[derive(Clone, Copy, Debug, Default, glsl_layout::Uniform)]
+pub struct Camera {
+    pub view: glsl_layout::mat4,
+    pub proj: glsl_layout::mat4,
+    pub position: glsl_layout::vec3,
+    pub orthographic: glsl_layout::boolean,
+    pub lights: [Light; 4],
+    pub weights: [glsl_layout::float; 2],
+}
+
+/// Buffer block `Particles` declared at `tests/test.glsl:19`.
+#[derive(Clone, Copy, Debug, Default, glsl_layout::Uniform)]
+#[glsl(layouts(std430))]
+pub struct Particles {
+    pub count: glsl_layout::uint,
+    pub velocities: [glsl_layout::vec2; 4],
+}
+
+/// Buffer block `Lights` declared at `tests/test_include.glsl:3`.
+///
+/// Runtime-sized array `items` is not included, use `UnsizedBlock` for it.
+#[derive(Clone, Copy, Debug, Default, glsl_layout::Uniform)]
+#[glsl(layouts(std430))]
+pub struct Lights {
+    pub count: glsl_layout::uint,
+}
+"
+    );
+}
+
+#[test]
+fn test_build_large_array() {
+    let dir = std::env::temp_dir().join(format!("glsl-layout-test-build-{}", std::process::id()));
+    let path = dir.join("kernel.glsl");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        &path,
+        "struct Samples { float weights[64]; };
+struct Kernel { Samples samples; };
+layout(std140) uniform Filter { Kernel kernel; float scale; };
+",
+    )
+    .unwrap();
+    let source = Builder::new().file(&path).generate().unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    // Arrays longer than 32 elements don't implement `Default`, neither do structures containing them.
+    for name in ["Samples", "Kernel", "Filter"] {
+        assert!(source.contains(&format!(
+            "#[derive(Clone, Copy, Debug, glsl_layout::Uniform)]\npub struct {} {{",
+            name
+        )));
+    }
+}
+
+#[test]
+fn test_declarations() {
+    use crate::layout::{member_layouts, Std430};
+
+    let declarations = declarations("tests/test_include.glsl").unwrap();
+    let names = declarations
+        .iter()
+        .map(|declaration| (declaration.kind, declaration.name))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("struct", "Light"),
+            ("uniform", "Camera"),
+            ("buffer", "Particles"),
+            ("buffer", "Lights"),
+        ]
+    );
+
+    let lights = &declarations[3];
+    assert_eq!(lights.packing, Some("std430"));
+    assert!(lights.runtime_sized);
+    let layouts = member_layouts::<Std430>(lights.ty);
+    assert_eq!(layouts[1].path, "items");
+    assert_eq!(layouts[1].offset, 16);
+    assert_eq!(layouts[1].array_stride, Some(16));
+}
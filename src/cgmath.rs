@@ -1,8 +1,12 @@
 use crate::mat::{dmat2, dmat3, dmat4, imat2, imat3, imat4, mat2, mat3, mat4, umat2, umat3, umat4};
+use crate::reflect::TypeInfo;
 use crate::scalar::{double, float, int, uint};
 use crate::uniform::Uniform;
 use crate::vec::{dvec2, dvec3, dvec4, ivec2, ivec3, ivec4, uvec2, uvec3, uvec4, vec2, vec3, vec4};
-use cgmath::{Matrix2, Matrix3, Matrix4, Vector2, Vector3, Vector4};
+use cgmath::{
+    Basis2, Basis3, Decomposed, Deg, Matrix2, Matrix3, Matrix4, Point2, Point3, Quaternion, Rad,
+    Rotation2, Rotation3, Vector2, Vector3, Vector4,
+};
 
 macro_rules! impl_vec_from_cgmath {
     ($vec:ident : $cgmath:ident => [$type:ty; $size:tt]) => {
@@ -13,9 +17,17 @@ macro_rules! impl_vec_from_cgmath {
             }
         }
 
+        impl From<$vec> for $cgmath<$type> {
+            fn from(value: $vec) -> Self {
+                let array: [$type; $size] = value.into();
+                array.into()
+            }
+        }
+
         impl Uniform for $cgmath<$type> {
             type Align = <$vec as Uniform>::Align;
             type Std140 = $vec;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
 
             fn std140(&self) -> Self::Std140 {
                 Self::Std140::from(*self)
@@ -33,9 +45,17 @@ macro_rules! impl_mat_from_cgmath {
             }
         }
 
+        impl From<$mat> for $cgmath<$type> {
+            fn from(value: $mat) -> Self {
+                let array: [[$type; $size]; $size] = value.into();
+                array.into()
+            }
+        }
+
         impl Uniform for $cgmath<$type> {
             type Align = <$mat as Uniform>::Align;
             type Std140 = $mat;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
 
             fn std140(&self) -> Self::Std140 {
                 let array: [[$type; $size]; $size] = (*self).into();
@@ -45,6 +65,68 @@ macro_rules! impl_mat_from_cgmath {
     };
 }
 
+macro_rules! impl_angle_from_cgmath {
+    ($scalar:ident : $cgmath:ident) => {
+        /// Angles are uploaded in radians.
+        impl Uniform for $cgmath<$scalar> {
+            type Align = <$scalar as Uniform>::Align;
+            type Std140 = $scalar;
+            const TYPE_INFO: TypeInfo = <$scalar as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Rad::from(*self).0
+            }
+        }
+    };
+}
+
+macro_rules! impl_basis_from_cgmath {
+    ($mat:ident : $cgmath:ident => $matrix:ident<$type:ty>) => {
+        impl From<$cgmath<$type>> for $mat {
+            fn from(value: $cgmath<$type>) -> Self {
+                $matrix::from(value).into()
+            }
+        }
+
+        impl Uniform for $cgmath<$type> {
+            type Align = <$mat as Uniform>::Align;
+            type Std140 = $mat;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_decomposed_from_cgmath {
+    ($mat:ident : $vector:ident, $rotation:ident => $matrix:ident<$type:ty>) => {
+        impl<R> From<Decomposed<$vector<$type>, R>> for $mat
+        where
+            R: $rotation<Scalar = $type>,
+        {
+            fn from(value: Decomposed<$vector<$type>, R>) -> Self {
+                $matrix::from(value).into()
+            }
+        }
+
+        /// Decomposed transforms are uploaded as homogeneous matrices.
+        impl<R> Uniform for Decomposed<$vector<$type>, R>
+        where
+            R: $rotation<Scalar = $type> + Copy,
+        {
+            type Align = <$mat as Uniform>::Align;
+            type Std140 = $mat;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
 impl_vec_from_cgmath!(ivec2 : Vector2 => [int;    2]);
 impl_vec_from_cgmath!(ivec3 : Vector3 => [int;    3]);
 impl_vec_from_cgmath!(ivec4 : Vector4 => [int;    4]);
@@ -58,6 +140,23 @@ impl_vec_from_cgmath!(dvec2 : Vector2 => [double; 2]);
 impl_vec_from_cgmath!(dvec3 : Vector3 => [double; 3]);
 impl_vec_from_cgmath!(dvec4 : Vector4 => [double; 4]);
 
+impl_vec_from_cgmath!(ivec2 : Point2 => [int;    2]);
+impl_vec_from_cgmath!(ivec3 : Point3 => [int;    3]);
+impl_vec_from_cgmath!(uvec2 : Point2 => [uint;   2]);
+impl_vec_from_cgmath!(uvec3 : Point3 => [uint;   3]);
+impl_vec_from_cgmath!( vec2 : Point2 => [float;  2]);
+impl_vec_from_cgmath!( vec3 : Point3 => [float;  3]);
+impl_vec_from_cgmath!(dvec2 : Point2 => [double; 2]);
+impl_vec_from_cgmath!(dvec3 : Point3 => [double; 3]);
+
+impl_vec_from_cgmath!( vec4 : Quaternion => [float;  4]);
+impl_vec_from_cgmath!(dvec4 : Quaternion => [double; 4]);
+
+impl_angle_from_cgmath!(float : Rad);
+impl_angle_from_cgmath!(float : Deg);
+impl_angle_from_cgmath!(double : Rad);
+impl_angle_from_cgmath!(double : Deg);
+
 impl_mat_from_cgmath!(imat2 : Matrix2 => [int;    2]);
 impl_mat_from_cgmath!(imat3 : Matrix3 => [int;    3]);
 impl_mat_from_cgmath!(imat4 : Matrix4 => [int;    4]);
@@ -71,6 +170,16 @@ impl_mat_from_cgmath!(dmat2 : Matrix2 => [double; 2]);
 impl_mat_from_cgmath!(dmat3 : Matrix3 => [double; 3]);
 impl_mat_from_cgmath!(dmat4 : Matrix4 => [double; 4]);
 
+impl_basis_from_cgmath!( mat2 : Basis2 => Matrix2<float>);
+impl_basis_from_cgmath!( mat3 : Basis3 => Matrix3<float>);
+impl_basis_from_cgmath!(dmat2 : Basis2 => Matrix2<double>);
+impl_basis_from_cgmath!(dmat3 : Basis3 => Matrix3<double>);
+
+impl_decomposed_from_cgmath!( mat3 : Vector2, Rotation2 => Matrix3<float>);
+impl_decomposed_from_cgmath!( mat4 : Vector3, Rotation3 => Matrix4<float>);
+impl_decomposed_from_cgmath!(dmat3 : Vector2, Rotation2 => Matrix3<double>);
+impl_decomposed_from_cgmath!(dmat4 : Vector3, Rotation3 => Matrix4<double>);
+
 #[test]
 fn test_cgmath() {
     use crate::uniform::Std140;
@@ -87,4 +196,49 @@ fn test_cgmath() {
     let gm3 = Matrix3::<f32>::identity();
     assert_eq!(m3.std140(), gm3.std140());
     assert_eq!(gm3.std140(), gm3_to_m3.std140());
+
+    assert_eq!(Vector3::<f32>::from(v3), gv3);
+    assert_eq!(
+        Vector2::<i32>::from(ivec2::from([1, 2])),
+        Vector2::new(1, 2)
+    );
+    assert_eq!(Matrix3::<f32>::from(m3), gm3);
+
+    let gm4 = Matrix4::<f64>::new(
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    );
+    assert_eq!(Matrix4::<f64>::from(dmat4::from(gm4)), gm4);
+}
+
+#[test]
+fn test_cgmath_extended() {
+    use cgmath::{One, Rotation3};
+
+    let p = Point3::new(1.0f32, 2.0, 3.0);
+    assert_eq!(p.std140(), vec3::from([1.0, 2.0, 3.0]));
+    assert_eq!(Point3::<f32>::from(vec3::from(p)), p);
+
+    let q = Quaternion::new(0.8f32, 0.0, 0.6, 0.0);
+    assert_eq!(q.std140(), vec4::from([0.0, 0.6, 0.0, 0.8]));
+    assert_eq!(Quaternion::<f32>::from(vec4::from(q)), q);
+
+    assert_eq!(Rad(1.5f32).std140(), 1.5);
+    assert_eq!(Deg(180.0f64).std140(), std::f64::consts::PI);
+
+    let basis = Basis3::from_angle_y(Rad(1.0f32));
+    assert_eq!(basis.std140(), mat3::from(Matrix3::from(basis)));
+
+    let transform = Decomposed {
+        scale: 2.0f32,
+        rot: q,
+        disp: Vector3::new(1.0, 2.0, 3.0),
+    };
+    assert_eq!(transform.std140(), mat4::from(Matrix4::from(transform)));
+
+    let transform = Decomposed {
+        scale: 1.0f64,
+        rot: Basis3::one(),
+        disp: Vector3::new(1.0, 2.0, 3.0),
+    };
+    assert_eq!(transform.std140(), dmat4::from(Matrix4::from(transform)));
 }
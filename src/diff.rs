@@ -0,0 +1,101 @@
+use crate::layout::{fields, Layout, LayoutRules, Std140};
+use crate::uniform::{Std140 as _, Uniform};
+use std::ops::Range;
+
+/// Byte ranges of `std140` representation of `T` that differ between `old` and `new`.
+/// See `diff_bytes`.
+pub fn diff<T: Uniform>(old: &T::Std140, new: &T::Std140, gap: usize) -> Vec<Range<usize>> {
+    diff_bytes::<T, Std140>(old.as_raw(), new.as_raw(), gap)
+}
+
+/// Byte ranges of `cached` `std140` bytes that must be rewritten to hold `value`.
+/// See `diff_bytes`.
+///
+/// # Panics
+///
+/// If `cached` is shorter than `T::STD140_SIZE`.
+pub fn diff_cached<T: Uniform>(cached: &[u8], value: &T, gap: usize) -> Vec<Range<usize>> {
+    diff_bytes::<T, Std140>(cached, value.std140().as_raw(), gap)
+}
+
+/// Byte ranges of `T` laid out with rules `L` that differ between `old` and `new`.
+///
+/// Each range covers whole scalars, vectors or matrix columns, padding bytes are never compared.
+/// Ranges separated by no more than `gap` bytes are merged.
+/// Ranges are sorted and don't overlap.
+///
+/// # Panics
+///
+/// If `old` or `new` is shorter than size of `T` laid out with rules `L`.
+pub fn diff_bytes<T, L>(old: &[u8], new: &[u8], gap: usize) -> Vec<Range<usize>>
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    let size = <T as Layout<L>>::SIZE;
+    let (old, new) = (&old[..size], &new[..size]);
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    fields::<L>(&T::TYPE_INFO, 0, &mut |field| {
+        if old[field.clone()] == new[field.clone()] {
+            return;
+        }
+        match ranges.last_mut() {
+            Some(last) if field.start <= last.end + gap => last.end = field.end,
+            _ => ranges.push(field),
+        }
+    });
+    ranges
+}
+
+#[test]
+fn test_diff() {
+    use crate as glsl_layout;
+    use crate::layout::Std430;
+    use crate::{float, mat3, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Params {
+        position: vec3,
+        scale: float,
+        rotation: mat3,
+        weights: [float; 4],
+    }
+
+    let old = Params::default();
+    let mut new = Params { scale: 1.0, ..old };
+    assert!(diff::<Params>(&old.std140(), &old.std140(), 0).is_empty());
+    assert!(diff::<Params>(&old.std140(), &new.std140(), 0)
+        .into_iter()
+        .eq(Some(12..16)));
+
+    // Padding of `rotation` columns is never compared.
+    // rotation: 16, 32 and 48, weights: 64, 80, 96 and 112.
+    new.rotation = [[0.0; 3], [0.0, 2.0, 0.0], [0.0; 3]].into();
+    new.weights = [0.0, 3.0, 0.0, 4.0];
+    let cached = old.std140();
+    assert_eq!(
+        diff_cached(cached.as_raw(), &new, 0),
+        [12..16, 32..44, 80..84, 112..116]
+    );
+    assert_eq!(
+        diff_cached(cached.as_raw(), &new, 16),
+        [12..44, 80..84, 112..116]
+    );
+    assert_eq!(diff_cached(cached.as_raw(), &new, 28), [12..44, 80..116]);
+    assert!(diff_cached(cached.as_raw(), &new, 36)
+        .into_iter()
+        .eq(Some(12..116)));
+
+    // weights: 64, 68, 72 and 76.
+    let old = Layout::<Std430>::to_bytes(&old);
+    let new = Layout::<Std430>::to_bytes(&new);
+    assert_eq!(
+        diff_bytes::<Params, Std430>(&old, &new, 0),
+        [12..16, 32..44, 68..72, 76..80]
+    );
+    assert_eq!(
+        diff_bytes::<Params, Std430>(&old, &new, 4),
+        [12..16, 32..44, 68..80]
+    );
+}
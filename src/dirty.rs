@@ -0,0 +1,59 @@
+use std::ops::Range;
+
+/// Sorted set of disjoint ranges. Adjacent ranges are merged.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DirtyRanges {
+    ranges: Vec<Range<usize>>,
+}
+
+impl DirtyRanges {
+    /// Mark range as dirty.
+    pub(crate) fn insert(&mut self, range: Range<usize>) {
+        if range.start >= range.end {
+            return;
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        self.ranges.retain(|dirty| {
+            if dirty.end < start || dirty.start > end {
+                true
+            } else {
+                start = start.min(dirty.start);
+                end = end.max(dirty.end);
+                false
+            }
+        });
+        let index = self.ranges.partition_point(|dirty| dirty.start < start);
+        self.ranges.insert(index, start..end);
+    }
+
+    /// Forget everything at or after `end`.
+    pub(crate) fn truncate(&mut self, end: usize) {
+        self.ranges.retain(|dirty| dirty.start < end);
+        if let Some(last) = self.ranges.last_mut() {
+            last.end = last.end.min(end);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.ranges.iter().cloned()
+    }
+}
+
+#[test]
+fn test_dirty_ranges() {
+    let mut dirty = DirtyRanges::default();
+    dirty.insert(4..6);
+    dirty.insert(0..1);
+    dirty.insert(8..9);
+    dirty.insert(6..7);
+    assert_eq!(dirty.iter().collect::<Vec<_>>(), [0..1, 4..7, 8..9]);
+    dirty.insert(1..8);
+    assert!(dirty.iter().eq(Some(0..9)));
+    dirty.truncate(5);
+    assert!(dirty.iter().eq(Some(0..5)));
+}
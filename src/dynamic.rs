@@ -0,0 +1,152 @@
+use crate::layout::round_up;
+use crate::uniform::Uniform;
+use std::{fmt, marker::PhantomData, ops::Range, ptr::read_unaligned};
+
+/// Array of `std140` values starting at multiples of runtime alignment,
+/// e.g. `minUniformBufferOffsetAlignment` or `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`.
+/// Elements can be bound one by one with dynamic offsets or `glBindBufferRange`.
+pub struct DynamicUniformArray<T> {
+    bytes: Vec<u8>,
+    stride: usize,
+    marker: PhantomData<fn(T)>,
+}
+
+impl<T> DynamicUniformArray<T>
+where
+    T: Uniform,
+{
+    /// Create array of `len` zeroed elements with given minimal alignment of their offsets.
+    ///
+    /// # Panics
+    ///
+    /// If `T` is zero-sized.
+    pub fn new(len: usize, min_alignment: usize) -> Self {
+        let align = min_alignment.max(T::STD140_ALIGN);
+        let stride = round_up(T::STD140_SIZE, align);
+        assert!(
+            stride > 0,
+            "`DynamicUniformArray` can't hold zero-sized values"
+        );
+        DynamicUniformArray {
+            bytes: vec![0; len * stride],
+            stride,
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / self.stride
+    }
+
+    /// Check if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Distance between starts of elements.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Change number of elements. New elements are zeroed.
+    pub fn resize(&mut self, len: usize) {
+        self.bytes.resize(len * self.stride, 0);
+    }
+
+    /// Offset of element to bind.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn offset_of(&self, index: usize) -> usize {
+        assert!(index < self.len(), "Index {} is out of bounds", index);
+        index * self.stride
+    }
+
+    /// Get element.
+    pub fn get(&self, index: usize) -> Option<T::Std140> {
+        if index < self.len() {
+            let bytes = &self.bytes[self.range(index)];
+            // Any bit pattern is valid for `Std140` types.
+            Some(unsafe { read_unaligned(bytes.as_ptr() as *const T::Std140) })
+        } else {
+            None
+        }
+    }
+
+    /// Replace element.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: &T) {
+        let range = self.range(index);
+        value.write_std140(&mut self.bytes[range]);
+    }
+
+    /// Bytes of all elements.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    fn range(&self, index: usize) -> Range<usize> {
+        let offset = self.offset_of(index);
+        offset..offset + T::STD140_SIZE
+    }
+}
+
+impl<T> Clone for DynamicUniformArray<T> {
+    fn clone(&self) -> Self {
+        DynamicUniformArray {
+            bytes: self.bytes.clone(),
+            stride: self.stride,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for DynamicUniformArray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DynamicUniformArray")
+            .field("bytes", &self.bytes.len())
+            .field("stride", &self.stride)
+            .finish()
+    }
+}
+
+#[test]
+fn test_dynamic_uniform_array() {
+    use crate as glsl_layout;
+    use crate::{float, mat4, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Object {
+        transform: mat4,
+        color: vec3,
+        scale: float,
+    }
+
+    let mut array = DynamicUniformArray::<Object>::new(3, 256);
+    assert_eq!(array.stride(), 256);
+    assert_eq!(array.len(), 3);
+    assert_eq!(array.as_bytes().len(), 768);
+    assert_eq!(array.offset_of(2), 512);
+
+    array.set(
+        1,
+        &Object {
+            scale: 2.0,
+            ..Default::default()
+        },
+    );
+    assert_eq!(array.get(1).unwrap().scale, 2.0);
+    assert_eq!(&array.as_bytes()[256 + 76..256 + 80], &2.0f32.to_ne_bytes());
+    assert!(array.get(3).is_none());
+
+    // Stride is never less than `std140` size.
+    let mut array = DynamicUniformArray::<Object>::new(2, 64);
+    assert_eq!(array.stride(), 128);
+    array.resize(4);
+    assert_eq!(array.offset_of(3), 384);
+}
@@ -1,24 +1,37 @@
-use crate::mat::{dmat2, dmat3, dmat4, mat2, mat3, mat4};
-use crate::scalar::{double, float, int, uint};
+use crate::mat::{dmat2, dmat3, dmat3x2, dmat4, dmat4x3, mat2, mat3, mat3x2, mat4, mat4x3};
+use crate::reflect::TypeInfo;
+use crate::scalar::{boolean, double, float, int, uint};
 use crate::uniform::Uniform;
-use crate::vec::{dvec2, dvec3, dvec4, ivec2, ivec3, ivec4, uvec2, uvec3, uvec4, vec2, vec3, vec4};
+use crate::vec::{
+    bvec2, bvec3, bvec4, dvec2, dvec3, dvec4, ivec2, ivec3, ivec4, uvec2, uvec3, uvec4, vec2, vec3,
+    vec4,
+};
 use glam::{
-    DMat2, DMat3, DMat4, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Mat2, Mat3, Mat4, UVec2, UVec3,
-    UVec4, Vec2, Vec3, Vec4,
+    Affine2, Affine3A, BVec2, BVec3, BVec3A, BVec4, BVec4A, DAffine2, DAffine3, DMat2, DMat3,
+    DMat4, DQuat, DVec2, DVec3, DVec4, IVec2, IVec3, IVec4, Mat2, Mat3, Mat3A, Mat4, Quat, UVec2,
+    UVec3, UVec4, Vec2, Vec3, Vec3A, Vec4,
 };
 
 macro_rules! impl_vec_from_glam {
     ($vec:ident : $glam:ident => [$type:ty; $size:tt]) => {
         impl From<$glam> for $vec {
             fn from(value: $glam) -> Self {
-                let array: [$type; $size] = value.into();
+                let array: [$type; $size] = value.to_array();
                 array.into()
             }
         }
 
+        impl From<$vec> for $glam {
+            fn from(value: $vec) -> Self {
+                let array: [$type; $size] = value.into();
+                $glam::from_array(array)
+            }
+        }
+
         impl Uniform for $glam {
             type Align = <$vec as Uniform>::Align;
             type Std140 = <$vec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
 
             fn std140(&self) -> Self::Std140 {
                 Self::Std140::from(*self)
@@ -35,9 +48,17 @@ macro_rules! impl_mat_from_glam {
             }
         }
 
+        impl From<$mat> for $glam {
+            fn from(value: $mat) -> Self {
+                let array: [[$type; $size]; $size] = value.into();
+                $glam::from_cols_array_2d(&array)
+            }
+        }
+
         impl Uniform for $glam {
             type Align = <$mat as Uniform>::Align;
             type Std140 = <$mat as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
 
             fn std140(&self) -> Self::Std140 {
                 Self::Std140::from(self.to_cols_array_2d())
@@ -46,6 +67,75 @@ macro_rules! impl_mat_from_glam {
     };
 }
 
+macro_rules! impl_bvec_from_glam {
+    ($bvec:ident : $glam:ident => [$($c:ident),+; $size:tt]) => {
+        impl From<$glam> for $bvec {
+            fn from(value: $glam) -> Self {
+                let array: [bool; $size] = value.into();
+                array.into()
+            }
+        }
+
+        impl From<$bvec> for $glam {
+            fn from(value: $bvec) -> Self {
+                let [$($c),+]: [boolean; $size] = value.into();
+                $glam::new($($c.into()),+)
+            }
+        }
+
+        impl Uniform for $glam {
+            type Align = <$bvec as Uniform>::Align;
+            type Std140 = <$bvec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$bvec as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_affine_from_glam {
+    ($mat:ident, $matcxr:ident : $glam:ident => $glam_mat:ident::$from_mat:ident, [[$type:ty; $rows:tt]; $cols:tt]) => {
+        impl From<$glam> for $matcxr {
+            fn from(value: $glam) -> Self {
+                $matcxr::from(value.to_cols_array_2d())
+            }
+        }
+
+        impl From<$matcxr> for $glam {
+            fn from(value: $matcxr) -> Self {
+                let array: [[$type; $rows]; $cols] = value.into();
+                $glam::from_cols_array_2d(&array)
+            }
+        }
+
+        impl From<$glam> for $mat {
+            fn from(value: $glam) -> Self {
+                $mat::from($glam_mat::from(value))
+            }
+        }
+
+        /// Drops the last row of the matrix which is expected to be `(0, .., 0, 1)`.
+        impl From<$mat> for $glam {
+            fn from(value: $mat) -> Self {
+                $glam::$from_mat($glam_mat::from(value))
+            }
+        }
+
+        /// Affine transforms are uploaded as homogeneous matrices.
+        impl Uniform for $glam {
+            type Align = <$mat as Uniform>::Align;
+            type Std140 = <$mat as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                $mat::from(*self)
+            }
+        }
+    };
+}
+
 impl_vec_from_glam!(ivec2 : IVec2 => [int;    2]);
 impl_vec_from_glam!(ivec3 : IVec3 => [int;    3]);
 impl_vec_from_glam!(ivec4 : IVec4 => [int;    4]);
@@ -54,22 +144,35 @@ impl_vec_from_glam!(uvec3 : UVec3 => [uint;   3]);
 impl_vec_from_glam!(uvec4 : UVec4 => [uint;   4]);
 impl_vec_from_glam!( vec2 : Vec2 => [float;   2]);
 impl_vec_from_glam!( vec3 : Vec3 => [float;   3]);
+impl_vec_from_glam!( vec3 : Vec3A => [float;  3]);
 impl_vec_from_glam!( vec4 : Vec4 => [float;   4]);
 impl_vec_from_glam!(dvec2 : DVec2 => [double; 2]);
 impl_vec_from_glam!(dvec3 : DVec3 => [double; 3]);
 impl_vec_from_glam!(dvec4 : DVec4 => [double; 4]);
+impl_vec_from_glam!( vec4 : Quat => [float;   4]);
+impl_vec_from_glam!(dvec4 : DQuat => [double; 4]);
+
+impl_bvec_from_glam!(bvec2 : BVec2 => [x, y; 2]);
+impl_bvec_from_glam!(bvec3 : BVec3 => [x, y, z; 3]);
+impl_bvec_from_glam!(bvec4 : BVec4 => [x, y, z, w; 4]);
+impl_bvec_from_glam!(bvec3 : BVec3A => [x, y, z; 3]);
+impl_bvec_from_glam!(bvec4 : BVec4A => [x, y, z, w; 4]);
 
 impl_mat_from_glam!( mat2 : Mat2 => [float;   2]);
 impl_mat_from_glam!( mat3 : Mat3 => [float;   3]);
+impl_mat_from_glam!( mat3 : Mat3A => [float;  3]);
 impl_mat_from_glam!( mat4 : Mat4 => [float;   4]);
 impl_mat_from_glam!(dmat2 : DMat2 => [double; 2]);
 impl_mat_from_glam!(dmat3 : DMat3 => [double; 3]);
 impl_mat_from_glam!(dmat4 : DMat4 => [double; 4]);
 
+impl_affine_from_glam!( mat3,  mat3x2 : Affine2 => Mat3::from_mat3, [[float; 2]; 3]);
+impl_affine_from_glam!( mat4,  mat4x3 : Affine3A => Mat4::from_mat4, [[float; 3]; 4]);
+impl_affine_from_glam!(dmat3, dmat3x2 : DAffine2 => DMat3::from_mat3, [[double; 2]; 3]);
+impl_affine_from_glam!(dmat4, dmat4x3 : DAffine3 => DMat4::from_mat4, [[double; 3]; 4]);
+
 #[test]
 pub fn test_glam() {
-    use crate::uniform::Std140;
-
     let v3: vec3 = [1.0, 2.0, 3.0].into();
     let gv3_to_v3: vec3 = Vec3::new(1.0, 2.0, 3.0).into();
     let gv3 = Vec3::new(1.0, 2.0, 3.0);
@@ -81,4 +184,65 @@ pub fn test_glam() {
     let gm3 = Mat3::IDENTITY;
     assert_eq!(m3.std140(), gm3.std140());
     assert_eq!(gm3.std140(), gm3_to_m3.std140());
+
+    assert_eq!(Vec3::from(v3), gv3);
+    assert_eq!(IVec2::from(ivec2::from([1, 2])), IVec2::new(1, 2));
+    assert_eq!(Mat3::from(m3), gm3);
+
+    let gm4 = Mat4::from_cols_array(&[
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    ]);
+    assert_eq!(Mat4::from(mat4::from(gm4)), gm4);
+    assert_eq!(DMat2::from(dmat2::from(DMat2::IDENTITY)), DMat2::IDENTITY);
+}
+
+#[test]
+pub fn test_glam_extended() {
+    let v3a = Vec3A::new(1.0, 2.0, 3.0);
+    assert_eq!(v3a.std140(), Vec3::new(1.0, 2.0, 3.0).std140());
+    assert_eq!(Vec3A::from(vec3::from(v3a)), v3a);
+
+    let m3a = Mat3A::from_cols_array(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0]);
+    assert_eq!(m3a.std140(), Mat3::from(m3a).std140());
+    assert_eq!(Mat3A::from(mat3::from(m3a)), m3a);
+
+    let q = Quat::from_xyzw(0.0, 0.6, 0.0, 0.8);
+    assert_eq!(q.std140(), vec4::from([0.0, 0.6, 0.0, 0.8]));
+    assert_eq!(Quat::from(vec4::from(q)), q);
+    let dq = DQuat::from_xyzw(0.0, 0.6, 0.0, 0.8);
+    assert_eq!(DQuat::from(dq.std140()), dq);
+
+    let b = BVec3::new(true, false, true);
+    assert_eq!(
+        b.std140(),
+        bvec3::from([boolean::from(true), false.into(), true.into()])
+    );
+    assert_eq!(BVec3::from(bvec3::from(b)), b);
+    assert_eq!(
+        BVec4A::from(bvec4::from(BVec4A::new(false, true, true, false))).bitmask(),
+        0b0110
+    );
+
+    let a3 = Affine3A::from_scale_rotation_translation(
+        Vec3::new(1.0, 2.0, 3.0),
+        Quat::from_rotation_y(1.0),
+        Vec3::new(4.0, 5.0, 6.0),
+    );
+    assert_eq!(a3.std140(), Mat4::from(a3).std140());
+    let a3_cols: [[f32; 3]; 4] = mat4x3::from(a3).into();
+    assert_eq!(a3_cols, a3.to_cols_array_2d());
+    assert_eq!(Affine3A::from(mat4x3::from(a3)), a3);
+    assert_eq!(Affine3A::from(mat4::from(a3)), a3);
+
+    let a2 = Affine2::from_scale_angle_translation(Vec2::new(1.0, 2.0), 1.0, Vec2::new(3.0, 4.0));
+    assert_eq!(a2.std140(), Mat3::from(a2).std140());
+    assert_eq!(Affine2::from(mat3x2::from(a2)), a2);
+    assert_eq!(Affine2::from(mat3::from(a2)), a2);
+
+    let da3 = DAffine3::from_translation(DVec3::new(1.0, 2.0, 3.0));
+    assert_eq!(da3.std140(), DMat4::from(da3).std140());
+    assert_eq!(DAffine3::from(dmat4x3::from(da3)), da3);
+    let da2 = DAffine2::from_translation(DVec2::new(1.0, 2.0));
+    assert_eq!(DAffine2::from(dmat3::from(da2)), da2);
+    assert_eq!(DAffine2::from(dmat3x2::from(da2)), da2);
 }
@@ -0,0 +1,727 @@
+//! GLSL declarations of structures and interface blocks.
+//!
+//! Derived structures can be checked against declarations in GLSL files
+//! with `#[glsl(check = "path", block = "name")]`.
+//! The path is relative to the crate root, files it includes are searched as well.
+//! Blocks are checked with their own layout, standalone structures with rules
+//! listed in `#[glsl(layouts(...))]`, or `std140` if there are none.
+//!
+//! ```rust
+//! # use glsl_layout::{float, vec3, Uniform};
+//! #[derive(Clone, Copy, Uniform)]
+//! #[glsl(check = "tests/test_include.glsl", layouts(std140, std430))]
+//! struct Light {
+//!     position: vec3,
+//!     intensity: float,
+//! }
+//! ```
+//!
+//! Members with wrong names are rejected:
+//!
+//! ```rust,compile_fail
+//! # use glsl_layout::{float, vec3, Uniform};
+//! #[derive(Clone, Copy, Uniform)]
+//! #[glsl(check = "tests/test.glsl")]
+//! struct Light {
+//!     pos: vec3,
+//!     intensity: float,
+//! }
+//! ```
+//!
+//! As well as members of wrong types:
+//!
+//! ```rust,compile_fail
+//! # use glsl_layout::{float, vec4, Uniform};
+//! #[derive(Clone, Copy, Uniform)]
+//! #[glsl(check = "tests/test.glsl")]
+//! struct Light {
+//!     position: vec4,
+//!     intensity: float,
+//! }
+//! ```
+//!
+//! Members in wrong order:
+//!
+//! ```rust,compile_fail
+//! # use glsl_layout::{float, vec3, Uniform};
+//! #[derive(Clone, Copy, Uniform)]
+//! #[glsl(check = "tests/test.glsl")]
+//! struct Light {
+//!     intensity: float,
+//!     position: vec3,
+//! }
+//! ```
+//!
+//! And members that would move following members to wrong offsets:
+//!
+//! ```rust,compile_fail
+//! # use glsl_layout::{uvec3, vec2, Uniform};
+//! #[derive(Clone, Copy, Uniform)]
+//! #[glsl(check = "tests/test.glsl", block = "Particles", layouts(std430))]
+//! struct Particles {
+//!     count: uvec3,
+//!     velocities: [vec2; 4],
+//! }
+//! ```
+
+use crate::layout::{LayoutRules, Std140};
+use crate::reflect::{declare_members, ScalarType, StructInfo, TypeInfo};
+use crate::uniform::Uniform;
+use std::{fmt::Write, fs, io, path::Path};
+
+/// Generate GLSL declarations of structure `T` and structures it uses.
+///
+/// # Panics
+///
+/// If `T` is not a structure or different structures it uses have the same name.
+pub fn declaration<T: Uniform>() -> String {
+    if !matches!(T::TYPE_INFO, TypeInfo::Struct(_)) {
+        panic!("Only structures can be declared");
+    }
+
+    let mut source = String::new();
+    for (index, info) in T::TYPE_INFO.structs().into_iter().enumerate() {
+        if index > 0 {
+            source.push('\n');
+        }
+        declare_struct(&mut source, info);
+    }
+    source
+}
+
+/// Generate GLSL declaration of `uniform` block with members of `T`
+/// preceded by declarations of structures it uses.
+///
+/// # Panics
+///
+/// If `T` is not a structure or different structures it uses have the same name.
+pub fn uniform_block<T: Uniform>(name: &str, binding: Option<u32>) -> String {
+    block::<Std140>("uniform", name, binding, &T::TYPE_INFO, None)
+}
+
+/// Generate GLSL declaration of `buffer` block laid out with rules `L` with members of `T`
+/// preceded by declarations of structures it uses.
+///
+/// # Panics
+///
+/// If `T` is not a structure, glsl has no layout `L`
+/// or different structures used by `T` have the same name.
+pub fn buffer_block<T: Uniform, L: LayoutRules>(name: &str, binding: Option<u32>) -> String {
+    block::<L>("buffer", name, binding, &T::TYPE_INFO, None)
+}
+
+/// GLSL header with declarations of registered structures and blocks.
+/// Every structure is declared once, before structures and blocks that use it.
+/// Types must be registered explicitly, `derive(Uniform)` doesn't register them
+/// since Rust has no way to collect all derived types of a crate without extra dependencies.
+///
+/// ```no_run
+/// # use glsl_layout::*;
+/// #[derive(Clone, Copy, Default, Uniform)]
+/// struct Camera {
+///     view: mat4,
+///     proj: mat4,
+/// }
+///
+/// glsl::Header::new("GENERATED_UNIFORMS_GLSL")
+///     .uniform_block::<Camera>("Camera", Some(0))
+///     .write("shaders/generated/uniforms.glsl")
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct Header {
+    guard: String,
+    structs: Vec<&'static TypeInfo>,
+    blocks: Vec<HeaderBlock>,
+}
+
+#[derive(Clone, Debug)]
+struct HeaderBlock {
+    storage: &'static str,
+    qualifier: &'static str,
+    name: String,
+    binding: Option<u32>,
+    ty: &'static TypeInfo,
+    trailing: Option<(String, &'static TypeInfo)>,
+}
+
+impl Header {
+    /// Create empty header with given include guard macro.
+    pub fn new(guard: &str) -> Self {
+        Header {
+            guard: guard.to_owned(),
+            structs: Vec::new(),
+            blocks: Vec::new(),
+        }
+    }
+
+    /// Register structure `T`.
+    ///
+    /// # Panics
+    ///
+    /// If `T` is not a structure.
+    pub fn structure<T: Uniform>(mut self) -> Self {
+        if !matches!(T::TYPE_INFO, TypeInfo::Struct(_)) {
+            panic!("Only structures can be declared");
+        }
+        self.structs.push(&T::TYPE_INFO);
+        self
+    }
+
+    /// Register `uniform` block with members of `T`.
+    pub fn uniform_block<T: Uniform>(self, name: &str, binding: Option<u32>) -> Self {
+        self.block::<Std140>("uniform", name, binding, &T::TYPE_INFO, None)
+    }
+
+    /// Register `buffer` block laid out with rules `L` with members of `T`.
+    pub fn buffer_block<T: Uniform, L: LayoutRules>(
+        self,
+        name: &str,
+        binding: Option<u32>,
+    ) -> Self {
+        self.block::<L>("buffer", name, binding, &T::TYPE_INFO, None)
+    }
+
+    /// Register `buffer` block for `UnsizedBlock` with array named `items`.
+    pub fn unsized_block<H: Uniform, T: Uniform, L: LayoutRules>(
+        self,
+        name: &str,
+        items: &str,
+        binding: Option<u32>,
+    ) -> Self {
+        self.block::<L>(
+            "buffer",
+            name,
+            binding,
+            &H::TYPE_INFO,
+            Some((items, &T::TYPE_INFO)),
+        )
+    }
+
+    fn block<L: LayoutRules>(
+        mut self,
+        storage: &'static str,
+        name: &str,
+        binding: Option<u32>,
+        ty: &'static TypeInfo,
+        trailing: Option<(&str, &'static TypeInfo)>,
+    ) -> Self {
+        if !matches!(ty, TypeInfo::Struct(_)) {
+            panic!("Only structures can be declared as blocks");
+        }
+        self.blocks.push(HeaderBlock {
+            storage,
+            qualifier: L::GLSL_QUALIFIER.expect("Layout is not supported by glsl"),
+            name: name.to_owned(),
+            binding,
+            ty,
+            trailing: trailing.map(|(items, elem)| (items.to_owned(), elem)),
+        });
+        self
+    }
+
+    /// Generate source of the header.
+    ///
+    /// # Panics
+    ///
+    /// If different registered structures or structures they use have the same name.
+    pub fn source(&self) -> String {
+        let mut structs: Vec<&'static StructInfo> = Vec::new();
+        let mut add = |ty: &TypeInfo, skip_self: bool| {
+            let mut dependencies = ty.structs();
+            if skip_self {
+                dependencies.pop();
+            }
+            for dependency in dependencies {
+                if !dependency.is_in(&structs) {
+                    structs.push(dependency);
+                }
+            }
+        };
+        for ty in &self.structs {
+            add(ty, false);
+        }
+        for block in &self.blocks {
+            add(block.ty, true);
+            if let Some((_, elem)) = block.trailing {
+                add(elem, false);
+            }
+        }
+
+        let mut source = String::new();
+        writeln!(source, "#ifndef {}", self.guard).unwrap();
+        writeln!(source, "#define {}", self.guard).unwrap();
+        source.push_str("\n// Generated by glsl-layout.\n");
+        for info in structs {
+            source.push('\n');
+            declare_struct(&mut source, info);
+        }
+        for block in &self.blocks {
+            let info = match *block.ty {
+                TypeInfo::Struct(info) => info,
+                _ => unreachable!(),
+            };
+            source.push('\n');
+            declare_block(
+                &mut source,
+                block.storage,
+                block.qualifier,
+                &block.name,
+                block.binding,
+                info,
+                block
+                    .trailing
+                    .as_ref()
+                    .map(|(items, elem)| (items.as_str(), *elem)),
+            );
+        }
+        writeln!(source, "\n#endif // {}", self.guard).unwrap();
+        source
+    }
+
+    /// Write the header into file, creating parent directories.
+    /// The file is not touched if it has the same contents already,
+    /// so that shaders including it are not recompiled needlessly.
+    /// Returns `true` if the file was written.
+    ///
+    /// # Panics
+    ///
+    /// If different structures have the same name, as `source` does.
+    pub fn write(&self, path: impl AsRef<Path>) -> io::Result<bool> {
+        let path = path.as_ref();
+        let source = self.source();
+        if fs::read_to_string(path).is_ok_and(|old| old == source) {
+            return Ok(false);
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, source)?;
+        Ok(true)
+    }
+}
+
+/// Generate block declaration with members of `ty` and optional trailing runtime-sized array.
+pub(crate) fn block<L: LayoutRules>(
+    storage: &str,
+    name: &str,
+    binding: Option<u32>,
+    ty: &TypeInfo,
+    trailing: Option<(&str, &'static TypeInfo)>,
+) -> String {
+    let info = match *ty {
+        TypeInfo::Struct(info) => info,
+        _ => panic!("Only structures can be declared as blocks"),
+    };
+    let qualifier = L::GLSL_QUALIFIER.expect("Layout is not supported by glsl");
+
+    let mut structs = ty.structs();
+    structs.pop();
+    if let Some((_, elem)) = trailing {
+        for dependency in elem.structs() {
+            if !dependency.is_in(&structs) {
+                structs.push(dependency);
+            }
+        }
+    }
+
+    let mut source = String::new();
+    for dependency in structs {
+        declare_struct(&mut source, dependency);
+        source.push('\n');
+    }
+    declare_block(
+        &mut source,
+        storage,
+        qualifier,
+        name,
+        binding,
+        info,
+        trailing,
+    );
+    source
+}
+
+fn declare_block(
+    source: &mut String,
+    storage: &str,
+    qualifier: &str,
+    name: &str,
+    binding: Option<u32>,
+    info: &StructInfo,
+    trailing: Option<(&str, &TypeInfo)>,
+) {
+    write!(source, "layout({}", qualifier).unwrap();
+    if let Some(binding) = binding {
+        write!(source, ", binding = {}", binding).unwrap();
+    }
+    write!(source, ") {} {}", storage, name).unwrap();
+    declare_members(source, info, |ty, _| type_name(ty));
+
+    if let Some((items, mut elem)) = trailing {
+        let mut dimensions = String::from("[]");
+        while let TypeInfo::Array(inner, len) = *elem {
+            write!(dimensions, "[{}]", len).unwrap();
+            elem = inner;
+        }
+        // Insert before closing `};`.
+        source.truncate(source.len() - "};\n".len());
+        writeln!(source, "    {} {}{};", type_name(elem), items, dimensions).unwrap();
+        source.push_str("};\n");
+    }
+}
+
+fn declare_struct(source: &mut String, info: &StructInfo) {
+    source.push_str("struct ");
+    source.push_str(info.name);
+    declare_members(source, info, |ty, _| type_name(ty));
+}
+
+fn scalar_name(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "bool",
+        ScalarType::Int => "int",
+        ScalarType::Uint => "uint",
+        ScalarType::Float => "float",
+        ScalarType::Double => "double",
+    }
+}
+
+fn vector_prefix(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "b",
+        ScalarType::Int => "i",
+        ScalarType::Uint => "u",
+        ScalarType::Float => "",
+        ScalarType::Double => "d",
+    }
+}
+
+/// GLSL name of the type.
+/// Arrays are named by their elements followed by dimensions, i.e. `float[3][2]`.
+pub fn type_name(ty: &TypeInfo) -> String {
+    match *ty {
+        TypeInfo::Scalar(scalar) => scalar_name(scalar).to_owned(),
+        TypeInfo::Vector(scalar, len) => format!("{}vec{}", vector_prefix(scalar), len),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } if columns == rows => format!("{}mat{}", vector_prefix(scalar), columns),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => format!("{}mat{}x{}", vector_prefix(scalar), columns, rows),
+        TypeInfo::Array(mut elem, len) => {
+            let mut dimensions = format!("[{}]", len);
+            while let TypeInfo::Array(inner, len) = *elem {
+                write!(dimensions, "[{}]", len).unwrap();
+                elem = inner;
+            }
+            type_name(elem) + &dimensions
+        }
+        TypeInfo::Struct(info) => info.name.to_owned(),
+    }
+}
+
+#[test]
+fn test_glsl() {
+    use crate as glsl_layout;
+    use crate::layout::Std430;
+    use crate::{dmat2x3, float, ivec2, mat4, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Light {
+        position: vec3,
+        intensity: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Scene {
+        view: mat4,
+        lights: [Light; 4],
+        tiles: [[ivec2; 2]; 3],
+        basis: dmat2x3,
+    }
+
+    assert_eq!(
+        uniform_block::<Scene>("Scene", Some(2)),
+        "struct Light
+{
+    vec3 position;
+    float intensity;
+};
+
+layout(std140, binding = 2) uniform Scene
+{
+    mat4 view;
+    Light lights[4];
+    ivec2 tiles[3][2];
+    dmat2x3 basis;
+};
+"
+    );
+    assert_eq!(
+        buffer_block::<Light, Std430>("Lights", None),
+        "layout(std430) buffer Lights
+{
+    vec3 position;
+    float intensity;
+};
+"
+    );
+    assert_eq!(declaration::<Scene>(), {
+        let block = uniform_block::<Scene>("Scene", None);
+        block.replace("layout(std140) uniform Scene", "struct Scene")
+    });
+}
+
+#[test]
+fn test_check() {
+    use crate as glsl_layout;
+    use crate::{boolean, float, mat4, uint, vec2, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    #[glsl(check = "tests/test.glsl")]
+    struct Light {
+        position: vec3,
+        intensity: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    #[glsl(check = "tests/test.glsl", block = "Camera")]
+    struct CameraData {
+        view: mat4,
+        proj: mat4,
+        position: vec3,
+        orthographic: boolean,
+        lights: [Light; 4],
+        weights: [float; 2],
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    #[glsl(check = "tests/test.glsl", block = "Particles", layouts(std430))]
+    struct Particles {
+        count: uint,
+        velocities: [vec2; 4],
+    }
+
+    // Structure declared in included file, checked with both layouts.
+    #[derive(Clone, Copy, Default, Uniform)]
+    #[glsl(
+        check = "tests/test_include.glsl",
+        block = "Light",
+        layouts(std140, std430)
+    )]
+    struct IncludedLight {
+        position: vec3,
+        intensity: float,
+    }
+
+    assert_eq!(CameraData::STD140_SIZE, 240);
+}
+
+#[test]
+fn test_glsl_block() {
+    use crate as glsl_layout;
+    use crate::layout::{Layout, Std430};
+
+    crate::glsl_block! {
+        #define MAX_LIGHTS 2
+
+        struct Light {
+            /// Position in world space.
+            vec3 position;
+            float intensity;
+        };
+
+        layout(std140) uniform Camera {
+            mat4 view;
+            dmat2x3 proj;
+            bool enabled;
+            Light lights[MAX_LIGHTS];
+            float weights[3][2];
+        } camera;
+
+        layout(std430) buffer Particles {
+            uint count;
+            ivec2 cells[4];
+        };
+    }
+
+    // Structures containing large arrays, directly or not, don't derive `Default`.
+    mod large {
+        use crate as glsl_layout;
+
+        crate::glsl_block! {
+            struct Samples {
+                float weights[64];
+            };
+
+            struct Kernel {
+                Samples samples;
+            };
+
+            layout(std140) uniform Filter {
+                Kernel kernel;
+                float scale;
+            };
+        }
+    }
+    assert_eq!(large::Filter::STD140_SIZE, 1040);
+
+    let camera = Camera {
+        lights: [Light {
+            position: [1.0, 2.0, 3.0].into(),
+            intensity: 4.0,
+        }; MAX_LIGHTS],
+        ..Default::default()
+    };
+    assert_eq!(camera.lights[1].intensity, 4.0);
+    assert_eq!(<Particles as Layout<Std430>>::SIZE, 40);
+    assert_eq!(
+        uniform_block::<Camera>("Camera", None),
+        "struct Light
+{
+    vec3 position;
+    float intensity;
+};
+
+layout(std140) uniform Camera
+{
+    mat4 view;
+    dmat2x3 proj;
+    bool enabled;
+    Light lights[2];
+    float weights[3][2];
+};
+"
+    );
+}
+
+#[test]
+fn test_header() {
+    use crate as glsl_layout;
+    use crate::layout::Std430;
+    use crate::{float, mat4, uint, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Light {
+        position: vec3,
+        intensity: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Lights {
+        ambient: vec3,
+        lights: [Light; 2],
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Camera {
+        view: mat4,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Counter {
+        count: uint,
+    }
+
+    let header = Header::new("UNIFORMS_GLSL")
+        .structure::<Light>()
+        .uniform_block::<Camera>("Camera", Some(0))
+        .buffer_block::<Lights, Std430>("Lights", None)
+        .unsized_block::<Counter, Light, Std430>("Extra", "items", Some(2));
+    assert_eq!(
+        header.source(),
+        "#ifndef UNIFORMS_GLSL
+#define UNIFORMS_GLSL
+
+// Generated by glsl-layout.
+
+struct Light
+{
+    vec3 position;
+    float intensity;
+};
+
+layout(std140, binding = 0) uniform Camera
+{
+    mat4 view;
+};
+
+layout(std430) buffer Lights
+{
+    vec3 ambient;
+    Light lights[2];
+};
+
+layout(std430, binding = 2) buffer Extra
+{
+    uint count;
+    Light items[];
+};
+
+#endif // UNIFORMS_GLSL
+"
+    );
+
+    let dir = std::env::temp_dir().join(format!("glsl-layout-test-header-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let path = dir.join("generated/uniforms.glsl");
+    assert!(header.write(&path).unwrap());
+    assert!(!header.write(&path).unwrap());
+    assert_eq!(fs::read_to_string(&path).unwrap(), header.source());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_header_name_collision() {
+    use crate as glsl_layout;
+    use crate::float;
+    use std::panic::catch_unwind;
+
+    mod a {
+        use crate as glsl_layout;
+
+        #[derive(Clone, Copy, Default, glsl_layout::Uniform)]
+        pub struct Light {
+            pub position: glsl_layout::vec3,
+        }
+    }
+
+    mod b {
+        use crate as glsl_layout;
+
+        #[derive(Clone, Copy, Default, glsl_layout::Uniform)]
+        pub struct Light {
+            pub color: glsl_layout::vec4,
+        }
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Scene {
+        sun: a::Light,
+        lamp: b::Light,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Lamp {
+        light: b::Light,
+        intensity: float,
+    }
+
+    assert!(catch_unwind(|| Scene::TYPE_INFO.structs()).is_err());
+
+    let header = Header::new("LIGHTS")
+        .structure::<a::Light>()
+        .uniform_block::<Lamp>("Lamp", None);
+    assert!(catch_unwind(|| header.source()).is_err());
+
+    // The same structure is declared once.
+    let header = Header::new("LIGHTS")
+        .structure::<b::Light>()
+        .uniform_block::<Lamp>("Lamp", None);
+    assert_eq!(header.source().matches("struct Light").count(), 1);
+}
@@ -0,0 +1,162 @@
+//! HLSL constant buffer packing.
+//!
+//! HLSL packs `cbuffer` members into 16-byte registers.
+//! Scalars and vectors are aligned to their components but may not straddle a register.
+//! Arrays, matrices and structures start at a register boundary.
+//! Following members can be placed right after the last element of arrays and matrices,
+//! while structures force the next member into a new register.
+
+use crate::layout::{Hlsl, Layout};
+use crate::reflect::{declare_members, ScalarType, StructInfo, TypeInfo};
+use crate::uniform::Uniform;
+use std::fmt::Write;
+
+/// Size of `T` packed into HLSL constant buffer.
+pub const fn size_of<T: Uniform>() -> usize {
+    <T as Layout<Hlsl>>::SIZE
+}
+
+/// Write `value` packed into HLSL constant buffer into `out`.
+/// Padding bytes are zeroed.
+///
+/// # Panics
+///
+/// If `out` is shorter than `size_of::<T>()`.
+pub fn write<T: Uniform>(value: &T, out: &mut [u8]) {
+    Layout::<Hlsl>::write_layout(value, out)
+}
+
+/// Get `value` packed into HLSL constant buffer.
+/// Note that constant buffer views must be rounded up to 256 bytes.
+pub fn to_bytes<T: Uniform>(value: &T) -> Vec<u8> {
+    Layout::<Hlsl>::to_bytes(value)
+}
+
+/// Generate HLSL declaration of `cbuffer` with members of `T`
+/// preceded by declarations of structures it uses.
+///
+/// # Panics
+///
+/// If `T` is not a structure or different structures it uses have the same name.
+pub fn cbuffer<T: Uniform>(name: &str, register: Option<u32>) -> String {
+    let info = match T::TYPE_INFO {
+        TypeInfo::Struct(info) => info,
+        _ => panic!("Only structures can be declared as `cbuffer`"),
+    };
+
+    let mut source = String::new();
+    for dependency in T::TYPE_INFO.structs() {
+        if dependency.name != info.name {
+            declare_struct(&mut source, dependency);
+            source.push('\n');
+        }
+    }
+
+    source.push_str("cbuffer ");
+    source.push_str(name);
+    if let Some(register) = register {
+        write!(source, " : register(b{})", register).unwrap();
+    }
+    declare_members(&mut source, info, |ty, _| type_name(ty));
+    source
+}
+
+fn declare_struct(source: &mut String, info: &StructInfo) {
+    source.push_str("struct ");
+    source.push_str(info.name);
+    declare_members(source, info, |ty, _| type_name(ty));
+}
+
+fn scalar_name(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool => "bool",
+        ScalarType::Int => "int",
+        ScalarType::Uint => "uint",
+        ScalarType::Float => "float",
+        ScalarType::Double => "double",
+    }
+}
+
+fn type_name(ty: &TypeInfo) -> String {
+    match *ty {
+        TypeInfo::Scalar(scalar) => scalar_name(scalar).to_owned(),
+        TypeInfo::Vector(scalar, len) => format!("{}{}", scalar_name(scalar), len),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => format!("column_major {}{}x{}", scalar_name(scalar), rows, columns),
+        TypeInfo::Array(..) => unreachable!("Arrays are declared by their elements"),
+        TypeInfo::Struct(info) => info.name.to_owned(),
+    }
+}
+
+#[test]
+fn test_hlsl() {
+    use crate as glsl_layout;
+    use crate::{float, mat4, vec2, vec3, vec4};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Light {
+        position: vec3,
+        intensity: float,
+        color: vec3,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Camera {
+        view: mat4,
+        offset: vec2,
+        direction: vec4,
+        scale: vec2,
+        lights: [Light; 2],
+        exposure: float,
+        weights: [float; 3],
+        gamma: float,
+    }
+
+    // `color` ends at 28, `Light` is padded to two registers.
+    assert_eq!(size_of::<Light>(), 32);
+    // view: 0, offset: 64, direction: 80, scale: 96, lights: 112 and 144,
+    // exposure: 176, weights: 192, 208 and 224, gamma: 228.
+    assert_eq!(size_of::<Camera>(), 240);
+
+    let camera = Camera {
+        exposure: 2.0,
+        gamma: 3.0,
+        weights: [4.0, 5.0, 6.0],
+        ..Default::default()
+    };
+    let bytes = to_bytes(&camera);
+    let float_at = |offset: usize| {
+        let mut raw = [0; 4];
+        raw.copy_from_slice(&bytes[offset..offset + 4]);
+        f32::from_ne_bytes(raw)
+    };
+    assert_eq!(float_at(176), 2.0);
+    assert_eq!(float_at(208), 5.0);
+    assert_eq!(float_at(228), 3.0);
+
+    assert_eq!(
+        cbuffer::<Camera>("Camera", Some(1)),
+        "struct Light
+{
+    float3 position;
+    float intensity;
+    float3 color;
+};
+
+cbuffer Camera : register(b1)
+{
+    column_major float4x4 view;
+    float2 offset;
+    float4 direction;
+    float2 scale;
+    Light lights[2];
+    float exposure;
+    float weights[3];
+    float gamma;
+};
+"
+    );
+}
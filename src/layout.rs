@@ -0,0 +1,756 @@
+//! Memory layouts of glsl types.
+//!
+//! `LayoutRules` implementations define layouts and `Layout` trait
+//! lays out any `Uniform` type according to them.
+
+use crate::reflect::{MemberInfo, ScalarType, StructInfo, TypeInfo};
+use crate::uniform::{Std140 as _, Uniform};
+use std::mem::{size_of, MaybeUninit};
+use std::ops::Range;
+
+/// Rules that define memory layout of glsl types.
+/// Layouts differ in alignment of vectors, arrays and structures and in padding at their ends.
+pub trait LayoutRules {
+    /// Vectors of 2 components are aligned to their size,
+    /// vectors of 3 and 4 components are aligned to size of 4 components.
+    /// Otherwise vectors are aligned as their components.
+    const ALIGN_VECTORS: bool;
+
+    /// Minimal alignment of arrays and their elements.
+    const ARRAY_ALIGN: usize;
+
+    /// Minimal alignment of matrix columns.
+    const MATRIX_ALIGN: usize;
+
+    /// Minimal alignment of structures.
+    const STRUCT_ALIGN: usize;
+
+    /// Sizes of arrays and matrices are rounded up to their alignment.
+    /// Otherwise following members can be placed into the padding after their last element.
+    const PAD_ARRAY_TAIL: bool;
+
+    /// Sizes of structures are rounded up to their alignment.
+    /// Otherwise following members can be placed into the padding after their last member.
+    const PAD_STRUCT_TAIL: bool;
+
+    /// Scalars and vectors may not cross multiple of this value.
+    /// Zero means there is no such restriction.
+    const BOUNDARY: usize;
+
+    /// Vectors of 3 components occupy as much space as vectors of 4 components,
+    /// unless structure member is marked as packed.
+    const PAD_VEC3: bool;
+
+    /// Name of glsl layout qualifier, if glsl has this layout.
+    const GLSL_QUALIFIER: Option<&'static str> = None;
+}
+
+/// Rules of glsl's `layout(std140)`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Std140;
+
+impl LayoutRules for Std140 {
+    const ALIGN_VECTORS: bool = true;
+    const ARRAY_ALIGN: usize = 16;
+    const MATRIX_ALIGN: usize = 16;
+    const STRUCT_ALIGN: usize = 16;
+    const PAD_ARRAY_TAIL: bool = true;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 0;
+    const PAD_VEC3: bool = false;
+    const GLSL_QUALIFIER: Option<&'static str> = Some("std140");
+}
+
+/// Rules of glsl's `layout(std430)`.
+/// Arrays and structures are not aligned to 16 bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Std430;
+
+impl LayoutRules for Std430 {
+    const ALIGN_VECTORS: bool = true;
+    const ARRAY_ALIGN: usize = 1;
+    const MATRIX_ALIGN: usize = 1;
+    const STRUCT_ALIGN: usize = 1;
+    const PAD_ARRAY_TAIL: bool = true;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 0;
+    const PAD_VEC3: bool = false;
+    const GLSL_QUALIFIER: Option<&'static str> = Some("std430");
+}
+
+/// Rules of glsl's `layout(scalar)` from `GL_EXT_scalar_block_layout`.
+/// Everything is aligned as its components.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Scalar;
+
+impl LayoutRules for Scalar {
+    const ALIGN_VECTORS: bool = false;
+    const ARRAY_ALIGN: usize = 1;
+    const MATRIX_ALIGN: usize = 1;
+    const STRUCT_ALIGN: usize = 1;
+    const PAD_ARRAY_TAIL: bool = true;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 0;
+    const PAD_VEC3: bool = false;
+    const GLSL_QUALIFIER: Option<&'static str> = Some("scalar");
+}
+
+/// Packing rules of HLSL constant buffers.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Hlsl;
+
+impl LayoutRules for Hlsl {
+    const ALIGN_VECTORS: bool = false;
+    const ARRAY_ALIGN: usize = 16;
+    const MATRIX_ALIGN: usize = 16;
+    const STRUCT_ALIGN: usize = 16;
+    const PAD_ARRAY_TAIL: bool = false;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 16;
+    const PAD_VEC3: bool = false;
+}
+
+/// Layout of Metal Shading Language types. Follows rules of C.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Msl;
+
+impl LayoutRules for Msl {
+    const ALIGN_VECTORS: bool = true;
+    const ARRAY_ALIGN: usize = 1;
+    const MATRIX_ALIGN: usize = 1;
+    const STRUCT_ALIGN: usize = 1;
+    const PAD_ARRAY_TAIL: bool = true;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 0;
+    const PAD_VEC3: bool = true;
+}
+
+/// Layout of WGSL structures in `uniform` address space.
+/// Arrays and structures are aligned to 16 bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WgslUniform;
+
+impl LayoutRules for WgslUniform {
+    const ALIGN_VECTORS: bool = true;
+    const ARRAY_ALIGN: usize = 16;
+    const MATRIX_ALIGN: usize = 1;
+    const STRUCT_ALIGN: usize = 16;
+    const PAD_ARRAY_TAIL: bool = true;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 0;
+    const PAD_VEC3: bool = false;
+}
+
+/// Layout of WGSL structures in `storage` address space.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WgslStorage;
+
+impl LayoutRules for WgslStorage {
+    const ALIGN_VECTORS: bool = true;
+    const ARRAY_ALIGN: usize = 1;
+    const MATRIX_ALIGN: usize = 1;
+    const STRUCT_ALIGN: usize = 1;
+    const PAD_ARRAY_TAIL: bool = true;
+    const PAD_STRUCT_TAIL: bool = true;
+    const BOUNDARY: usize = 0;
+    const PAD_VEC3: bool = false;
+}
+
+/// `Uniform` type laid out with rules `L`.
+/// Implemented for all `Uniform` types.
+pub trait Layout<L: LayoutRules>: Uniform {
+    /// Size of the type.
+    const SIZE: usize;
+
+    /// Alignment of the type.
+    const ALIGN: usize;
+
+    /// Distance between starts of elements in array of the type.
+    const ARRAY_STRIDE: usize;
+
+    /// Write the value into `out`. Padding bytes are zeroed.
+    ///
+    /// # Panics
+    ///
+    /// If `out` is shorter than `SIZE`.
+    fn write_layout(&self, out: &mut [u8]);
+
+    /// Get bytes of the value.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; <Self as Layout<L>>::SIZE];
+        Layout::<L>::write_layout(self, &mut bytes);
+        bytes
+    }
+    /// Read `std140` representation of the value from `bytes`.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is shorter than `SIZE`.
+    fn read_layout(bytes: &[u8]) -> Self::Std140;
+}
+
+impl<T, L> Layout<L> for T
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    const SIZE: usize = type_layout::<L>(&T::TYPE_INFO).size;
+    const ALIGN: usize = type_layout::<L>(&T::TYPE_INFO).align;
+    const ARRAY_STRIDE: usize = array_stride::<L>(&T::TYPE_INFO, false);
+
+    fn write_layout(&self, out: &mut [u8]) {
+        write_relayout::<L>(
+            &T::TYPE_INFO,
+            self.std140().as_raw(),
+            &mut out[..<T as Layout<L>>::SIZE],
+        );
+    }
+
+    fn read_layout(bytes: &[u8]) -> T::Std140 {
+        let bytes = &bytes[..<T as Layout<L>>::SIZE];
+        let mut std140 = MaybeUninit::<T::Std140>::zeroed();
+        // All bytes are zeroed.
+        let raw = unsafe {
+            std::slice::from_raw_parts_mut(std140.as_mut_ptr() as *mut u8, size_of::<T::Std140>())
+        };
+        relayout::<L, Std140>(&T::TYPE_INFO, bytes, raw);
+        // Any bit pattern is valid for `Std140` types.
+        unsafe { std140.assume_init() }
+    }
+}
+
+/// Typed representation of `Uniform` type laid out with rules `L`.
+/// Representation for `std140` is `Uniform::Std140`, others are generated by
+/// `#[glsl(layouts(std430, scalar))]` attribute next to `derive(Uniform)`.
+pub trait Represent<L: LayoutRules>: Layout<L> {
+    /// Representation of the type.
+    /// Derived representations are byte arrays aligned to 1 byte, not to `Layout::ALIGN`.
+    type Repr: Copy;
+
+    /// Get representation of the value.
+    fn represent(&self) -> Self::Repr;
+}
+
+impl<T> Represent<Std140> for T
+where
+    T: Uniform,
+{
+    type Repr = T::Std140;
+
+    fn represent(&self) -> T::Std140 {
+        self.std140()
+    }
+}
+
+/// Placement of structure member, or member of nested structure, laid out with some rules.
+#[derive(Clone, Debug)]
+pub struct MemberLayout {
+    /// Member name. Members of nested structures are prefixed with names of their parents,
+    /// i.e. `lights[0].position`. Only first element of array is listed.
+    pub path: String,
+
+    /// Type of the member.
+    pub ty: &'static TypeInfo,
+
+    /// Offset from the start of the outermost structure.
+    pub offset: usize,
+
+    /// Size of the member.
+    pub size: usize,
+
+    /// Distance between elements if member is an array.
+    pub array_stride: Option<usize>,
+
+    /// Distance between columns if member is a matrix or array of matrices.
+    pub matrix_stride: Option<usize>,
+
+    /// Number of padding bytes between the member and the next member or the end of the structure.
+    pub padding: usize,
+}
+
+/// Layouts of members of the structure and structures nested in it, in order of their offsets.
+///
+/// # Panics
+///
+/// If `ty` is not a structure.
+pub fn member_layouts<L: LayoutRules>(ty: &TypeInfo) -> Vec<MemberLayout> {
+    fn collect<L: LayoutRules>(
+        info: &StructInfo,
+        prefix: &str,
+        base: usize,
+        layouts: &mut Vec<MemberLayout>,
+    ) {
+        let size = struct_layout::<L>(info).size;
+        let members = members::<L>(info).collect::<Vec<_>>();
+        for (index, &(member, offset, layout)) in members.iter().enumerate() {
+            let next = members.get(index + 1).map_or(size, |&(_, next, _)| next);
+
+            let mut elem = member.ty;
+            let mut first = String::new();
+            while let TypeInfo::Array(inner, _) = *elem {
+                elem = inner;
+                first.push_str("[0]");
+            }
+            let array_stride = match *member.ty {
+                TypeInfo::Array(inner, _) => Some(array_stride::<L>(inner, member.packed)),
+                _ => None,
+            };
+            let matrix_stride = match *elem {
+                TypeInfo::Matrix { scalar, rows, .. } => Some(matrix_stride::<L>(scalar, rows)),
+                _ => None,
+            };
+
+            let path = format!("{}{}", prefix, member.name);
+            layouts.push(MemberLayout {
+                path: path.clone(),
+                ty: member.ty,
+                offset: base + offset,
+                size: layout.size,
+                array_stride,
+                matrix_stride,
+                padding: next - (offset + layout.size),
+            });
+
+            if let TypeInfo::Struct(nested) = *elem {
+                collect::<L>(
+                    nested,
+                    &format!("{}{}.", path, first),
+                    base + offset,
+                    layouts,
+                );
+            }
+        }
+    }
+
+    let info = match *ty {
+        TypeInfo::Struct(info) => info,
+        _ => panic!("Only structures have members"),
+    };
+    let mut layouts = Vec::new();
+    collect::<L>(info, "", 0, &mut layouts);
+    layouts
+}
+
+/// Alignment and size of a type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TypeLayout {
+    pub(crate) align: usize,
+    pub(crate) size: usize,
+}
+
+pub(crate) const fn round_up(value: usize, align: usize) -> usize {
+    if align <= 1 {
+        value
+    } else {
+        value.div_ceil(align) * align
+    }
+}
+
+const fn max(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+const fn vector_layout<L: LayoutRules>(scalar: ScalarType, len: usize, packed: bool) -> TypeLayout {
+    let size = scalar.size();
+    if packed && L::PAD_VEC3 {
+        return TypeLayout {
+            align: size,
+            size: size * len,
+        };
+    }
+
+    let align = match (L::ALIGN_VECTORS, len) {
+        (false, _) | (true, 1) => size,
+        (true, 2) => size * 2,
+        (true, _) => size * 4,
+    };
+    let len = if L::PAD_VEC3 && len == 3 { 4 } else { len };
+    TypeLayout {
+        align,
+        size: size * len,
+    }
+}
+
+/// Layout of `len` elements placed one after another.
+const fn sequence_layout<L: LayoutRules>(
+    elem: TypeLayout,
+    len: usize,
+    min_align: usize,
+) -> TypeLayout {
+    let align = max(elem.align, min_align);
+    let stride = round_up(elem.size, align);
+    let size = if len == 0 {
+        0
+    } else if L::PAD_ARRAY_TAIL {
+        stride * len
+    } else {
+        stride * (len - 1) + elem.size
+    };
+    TypeLayout { align, size }
+}
+
+/// Layout of the type.
+pub(crate) const fn type_layout<L: LayoutRules>(ty: &TypeInfo) -> TypeLayout {
+    packed_layout::<L>(ty, false)
+}
+
+/// Layout of the type. Vectors and arrays of vectors may be packed.
+const fn packed_layout<L: LayoutRules>(ty: &TypeInfo, packed: bool) -> TypeLayout {
+    match *ty {
+        TypeInfo::Scalar(scalar) => vector_layout::<L>(scalar, 1, packed),
+        TypeInfo::Vector(scalar, len) => vector_layout::<L>(scalar, len, packed),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => sequence_layout::<L>(
+            vector_layout::<L>(scalar, rows, false),
+            columns,
+            L::MATRIX_ALIGN,
+        ),
+        TypeInfo::Array(elem, len) => {
+            sequence_layout::<L>(packed_layout::<L>(elem, packed), len, L::ARRAY_ALIGN)
+        }
+        TypeInfo::Struct(info) => struct_layout::<L>(info),
+    }
+}
+
+/// Layout of the structure member.
+pub(crate) const fn member_layout<L: LayoutRules>(member: &MemberInfo) -> TypeLayout {
+    packed_layout::<L>(member.ty, member.packed)
+}
+
+/// Distance between starts of array elements.
+pub(crate) const fn array_stride<L: LayoutRules>(elem: &TypeInfo, packed: bool) -> usize {
+    let elem = packed_layout::<L>(elem, packed);
+    round_up(elem.size, max(elem.align, L::ARRAY_ALIGN))
+}
+
+/// Distance between starts of matrix columns.
+pub(crate) const fn matrix_stride<L: LayoutRules>(scalar: ScalarType, rows: usize) -> usize {
+    let column = vector_layout::<L>(scalar, rows, false);
+    round_up(column.size, max(column.align, L::MATRIX_ALIGN))
+}
+
+/// Offset of a value placed at the first suitable position not before `offset`.
+const fn place<L: LayoutRules>(offset: usize, ty: &TypeInfo, layout: TypeLayout) -> usize {
+    let offset = round_up(offset, layout.align);
+    match *ty {
+        TypeInfo::Scalar(_) | TypeInfo::Vector(..)
+            if L::BOUNDARY != 0
+                && layout.size != 0
+                && offset / L::BOUNDARY != (offset + layout.size - 1) / L::BOUNDARY =>
+        {
+            round_up(offset, L::BOUNDARY)
+        }
+        _ => offset,
+    }
+}
+
+/// Layout of the structure.
+pub(crate) const fn struct_layout<L: LayoutRules>(info: &StructInfo) -> TypeLayout {
+    let mut align = max(L::STRUCT_ALIGN, 1);
+    let mut end = 0;
+    let mut index = 0;
+    while index < info.members.len() {
+        let member = &info.members[index];
+        let layout = member_layout::<L>(member);
+        end = place::<L>(end, member.ty, layout) + layout.size;
+        align = max(align, layout.align);
+        index += 1;
+    }
+
+    TypeLayout {
+        align,
+        size: if L::PAD_STRUCT_TAIL {
+            round_up(end, align)
+        } else {
+            end
+        },
+    }
+}
+
+/// Offset of the structure member with given index.
+///
+/// # Panics
+///
+/// If `ty` is not a structure or has no such member.
+#[doc(hidden)]
+pub const fn member_offset<L: LayoutRules>(ty: &TypeInfo, index: usize) -> usize {
+    let info = match *ty {
+        TypeInfo::Struct(info) => info,
+        _ => panic!("Type must be a structure"),
+    };
+    let mut end = 0;
+    let mut current = 0;
+    loop {
+        let member = &info.members[current];
+        let layout = member_layout::<L>(member);
+        let offset = place::<L>(end, member.ty, layout);
+        if current == index {
+            return offset;
+        }
+        end = offset + layout.size;
+        current += 1;
+    }
+}
+
+/// Members of the structure with their offsets and layouts.
+pub(crate) fn members<L: LayoutRules>(
+    info: &StructInfo,
+) -> impl Iterator<Item = (&MemberInfo, usize, TypeLayout)> {
+    info.members.iter().scan(0, |end, member| {
+        let layout = member_layout::<L>(member);
+        let offset = place::<L>(*end, member.ty, layout);
+        *end = offset + layout.size;
+        Some((member, offset, layout))
+    })
+}
+
+/// Zero `out` and write `std140` representation of the value of the type into it
+/// laid out with rules `L`.
+pub(crate) fn write_relayout<L: LayoutRules>(ty: &TypeInfo, std140: &[u8], out: &mut [u8]) {
+    out.fill(0);
+    relayout::<Std140, L>(ty, std140, out);
+}
+
+/// Copy value of the type from `src` laid out with rules `S` into `dst` laid out with rules `D`.
+/// Padding bytes of `dst` are left untouched.
+pub(crate) fn relayout<S: LayoutRules, D: LayoutRules>(ty: &TypeInfo, src: &[u8], dst: &mut [u8]) {
+    packed_relayout::<S, D>(ty, false, src, dst)
+}
+
+fn packed_relayout<S: LayoutRules, D: LayoutRules>(
+    ty: &TypeInfo,
+    packed: bool,
+    src: &[u8],
+    dst: &mut [u8],
+) {
+    match *ty {
+        TypeInfo::Scalar(scalar) => {
+            let size = scalar.size();
+            dst[..size].copy_from_slice(&src[..size]);
+        }
+        TypeInfo::Vector(scalar, len) => {
+            let size = scalar.size() * len;
+            dst[..size].copy_from_slice(&src[..size]);
+        }
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => {
+            let column = TypeInfo::Vector(scalar, rows);
+            let src_stride = matrix_stride::<S>(scalar, rows);
+            let dst_stride = matrix_stride::<D>(scalar, rows);
+            for index in 0..columns {
+                relayout::<S, D>(
+                    &column,
+                    &src[index * src_stride..],
+                    &mut dst[index * dst_stride..],
+                );
+            }
+        }
+        TypeInfo::Array(elem, len) => {
+            let src_stride = array_stride::<S>(elem, packed);
+            let dst_stride = array_stride::<D>(elem, packed);
+            for index in 0..len {
+                packed_relayout::<S, D>(
+                    elem,
+                    packed,
+                    &src[index * src_stride..],
+                    &mut dst[index * dst_stride..],
+                );
+            }
+        }
+        TypeInfo::Struct(info) => {
+            for ((member, src_offset, _), (_, dst_offset, _)) in
+                members::<S>(info).zip(members::<D>(info))
+            {
+                packed_relayout::<S, D>(
+                    member.ty,
+                    member.packed,
+                    &src[src_offset..],
+                    &mut dst[dst_offset..],
+                );
+            }
+        }
+    }
+}
+
+/// Visit byte ranges of scalars, vectors and matrix columns of the type laid out with rules `L`
+/// in order of their offsets. Padding bytes are not included.
+pub(crate) fn fields<L: LayoutRules>(
+    ty: &TypeInfo,
+    offset: usize,
+    f: &mut impl FnMut(Range<usize>),
+) {
+    packed_fields::<L>(ty, false, offset, f)
+}
+
+fn packed_fields<L: LayoutRules>(
+    ty: &TypeInfo,
+    packed: bool,
+    offset: usize,
+    f: &mut impl FnMut(Range<usize>),
+) {
+    match *ty {
+        TypeInfo::Scalar(scalar) => f(offset..offset + scalar.size()),
+        TypeInfo::Vector(scalar, len) => f(offset..offset + scalar.size() * len),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => {
+            let stride = matrix_stride::<L>(scalar, rows);
+            for index in 0..columns {
+                let start = offset + index * stride;
+                f(start..start + scalar.size() * rows);
+            }
+        }
+        TypeInfo::Array(elem, len) => {
+            let stride = array_stride::<L>(elem, packed);
+            for index in 0..len {
+                packed_fields::<L>(elem, packed, offset + index * stride, f);
+            }
+        }
+        TypeInfo::Struct(info) => {
+            for (member, member_offset, _) in members::<L>(info) {
+                packed_fields::<L>(member.ty, member.packed, offset + member_offset, f);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_std140_layout() {
+    use crate::{dmat4x3, dvec3, mat2, mat3, uint, vec3, Std140 as _, Uniform};
+
+    fn check<T: Uniform>(value: T) {
+        let std140 = value.std140();
+        assert_eq!(
+            type_layout::<Std140>(&T::TYPE_INFO).size,
+            size_of::<T::Std140>()
+        );
+        let mut bytes = vec![0; size_of::<T::Std140>()];
+        relayout::<Std140, Std140>(&T::TYPE_INFO, std140.as_raw(), &mut bytes);
+        // Padding bytes are not copied.
+        for (copied, raw) in bytes.iter().zip(std140.as_raw()) {
+            assert!(copied == raw || *copied == 0);
+        }
+    }
+
+    check(1.0f32);
+    check(vec3::from([1.0, 2.0, 3.0]));
+    check(dvec3::from([1.0, 2.0, 3.0]));
+    check(mat2::from([[1.0, 2.0], [3.0, 4.0]]));
+    check(mat3::from([
+        [1.0, 2.0, 3.0],
+        [4.0, 5.0, 6.0],
+        [7.0, 8.0, 9.0],
+    ]));
+    check(dmat4x3::from([[1.0, 2.0, 3.0]; 4]));
+    check([1u32, 2, 3]);
+    check([[uint::default(); 3]; 2]);
+    check([vec3::from([1.0, 2.0, 3.0]); 5]);
+}
+
+#[test]
+fn test_layouts() {
+    use crate as glsl_layout;
+    use crate::{float, vec2, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    #[glsl(layouts(std140, std430, scalar))]
+    struct Particle {
+        mass: float,
+        velocity: vec2,
+        position: vec3,
+        weights: [float; 2],
+    }
+
+    // mass: 0, velocity: 8, position: 16, weights: 32 and 48.
+    assert_eq!(<Particle as Layout<Std140>>::SIZE, 64);
+    // mass: 0, velocity: 8, position: 16, weights: 28 and 32.
+    assert_eq!(<Particle as Layout<Std430>>::SIZE, 48);
+    assert_eq!(<Particle as Layout<Std430>>::ALIGN, 16);
+    // mass: 0, velocity: 4, position: 12, weights: 24 and 28.
+    assert_eq!(<Particle as Layout<Scalar>>::SIZE, 32);
+    assert_eq!(<Particle as Layout<Scalar>>::ALIGN, 4);
+
+    let particle = Particle {
+        mass: 1.0,
+        velocity: [2.0, 3.0].into(),
+        position: [4.0, 5.0, 6.0].into(),
+        weights: [7.0, 8.0],
+    };
+    let floats = |bytes: &[u8]| {
+        bytes
+            .chunks(4)
+            .map(|chunk| f32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect::<Vec<_>>()
+    };
+
+    let std430 = Represent::<Std430>::represent(&particle);
+    assert_eq!(std430.as_ref(), &Layout::<Std430>::to_bytes(&particle)[..]);
+    assert_eq!(
+        floats(std430.as_ref()),
+        [1.0, 0.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 0.0, 0.0, 0.0]
+    );
+
+    let scalar = Represent::<Scalar>::represent(&particle);
+    assert_eq!(
+        floats(scalar.as_ref()),
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]
+    );
+
+    let std140 = Represent::<Std140>::represent(&particle);
+    assert_eq!(std140.as_raw().len(), 64);
+}
+
+#[test]
+fn test_member_layouts() {
+    use crate as glsl_layout;
+    use crate::{float, mat3, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Light {
+        position: vec3,
+        intensity: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Scene {
+        exposure: float,
+        lights: [Light; 2],
+        rotation: mat3,
+    }
+
+    let layouts = member_layouts::<Std430>(&Scene::TYPE_INFO)
+        .into_iter()
+        .map(|member| {
+            (
+                member.path,
+                member.offset,
+                member.size,
+                member.array_stride,
+                member.matrix_stride,
+                member.padding,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        layouts,
+        [
+            ("exposure".to_owned(), 0, 4, None, None, 12),
+            ("lights".to_owned(), 16, 32, Some(16), None, 0),
+            ("lights[0].position".to_owned(), 16, 12, None, None, 0),
+            ("lights[0].intensity".to_owned(), 28, 4, None, None, 0),
+            ("rotation".to_owned(), 48, 48, None, Some(16), 0),
+        ]
+    );
+}
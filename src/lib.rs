@@ -65,8 +65,27 @@ mod vec;
 
 #[macro_use]
 mod array;
+mod bounded;
+mod diff;
+mod dirty;
+mod dynamic;
+pub mod layout;
 mod mat;
+mod option;
+pub mod reflect;
+mod ring;
+mod slab;
 mod uniform;
+mod uniform_vec;
+mod unsized_block;
+
+pub mod glsl;
+pub mod hlsl;
+pub mod msl;
+pub mod wgsl;
+
+#[cfg(feature = "build")]
+pub mod build;
 
 #[cfg(feature = "cgmath")]
 mod cgmath;
@@ -77,10 +96,22 @@ mod nalgebra;
 #[cfg(feature = "glam")]
 mod glam;
 
+#[cfg(feature = "mint")]
+mod mint;
+
 pub use array::*;
+pub use bounded::*;
+pub use diff::*;
+pub use dynamic::*;
+pub use layout::{Layout, LayoutRules, Represent};
 pub use mat::*;
+pub use option::*;
+pub use ring::*;
 pub use scalar::*;
+pub use slab::*;
 pub use uniform::*;
+pub use uniform_vec::*;
+pub use unsized_block::*;
 pub use vec::*;
 
 #[allow(unused_imports)]
@@ -92,6 +123,7 @@ pub use glsl_layout_derive::*;
 #[test]
 fn test_derive() {
     use crate as glsl_layout;
+    #[allow(dead_code)]
     #[derive(Copy, Clone, Uniform)]
     struct Test {
         a: [u32; 3],
@@ -103,6 +135,7 @@ fn test_derive() {
 #[test]
 fn test_array() {
     use crate as glsl_layout;
+    #[allow(dead_code)]
     #[derive(Copy, Clone, Uniform)]
     struct Test {
         a: [u32; 3],
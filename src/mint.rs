@@ -0,0 +1,183 @@
+use crate::mat::{
+    dmat2x2, dmat2x3, dmat2x4, dmat3x2, dmat3x3, dmat3x4, dmat4x2, dmat4x3, dmat4x4, imat2x2,
+    imat2x3, imat2x4, imat3x2, imat3x3, imat3x4, imat4x2, imat4x3, imat4x4, mat2x2, mat2x3, mat2x4,
+    mat3x2, mat3x3, mat3x4, mat4x2, mat4x3, mat4x4, umat2x2, umat2x3, umat2x4, umat3x2, umat3x3,
+    umat3x4, umat4x2, umat4x3, umat4x4,
+};
+use crate::reflect::TypeInfo;
+use crate::scalar::{double, float, int, uint};
+use crate::uniform::Uniform;
+use crate::vec::{dvec2, dvec3, dvec4, ivec2, ivec3, ivec4, uvec2, uvec3, uvec4, vec2, vec3, vec4};
+use mint::{
+    ColumnMatrix2, ColumnMatrix2x3, ColumnMatrix2x4, ColumnMatrix3, ColumnMatrix3x2,
+    ColumnMatrix3x4, ColumnMatrix4, ColumnMatrix4x2, ColumnMatrix4x3, Point2, Point3, Quaternion,
+    RowMatrix2, RowMatrix2x3, RowMatrix2x4, RowMatrix3, RowMatrix3x2, RowMatrix3x4, RowMatrix4,
+    RowMatrix4x2, RowMatrix4x3, Vector2, Vector3, Vector4,
+};
+
+macro_rules! impl_vec_from_mint {
+    ($vec:ident : $mint:ident => [$type:ty; $size:tt]) => {
+        impl From<$mint<$type>> for $vec {
+            fn from(value: $mint<$type>) -> Self {
+                let array: [$type; $size] = value.into();
+                array.into()
+            }
+        }
+
+        impl From<$vec> for $mint<$type> {
+            fn from(value: $vec) -> Self {
+                let array: [$type; $size] = value.into();
+                array.into()
+            }
+        }
+
+        impl Uniform for $mint<$type> {
+            type Align = <$vec as Uniform>::Align;
+            type Std140 = <$vec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
+/// `mint` names matrices by rows x columns, glsl does it the other way around.
+/// Row-major matrices are transposed into column-major ones.
+macro_rules! impl_mat_from_mint {
+    ($mat:ident : $column:ident, $row:ident => [[$type:ty; $rows:tt]; $cols:tt]) => {
+        impl From<$column<$type>> for $mat {
+            fn from(value: $column<$type>) -> Self {
+                let array: [[$type; $rows]; $cols] = value.into();
+                array.into()
+            }
+        }
+
+        impl From<$mat> for $column<$type> {
+            fn from(value: $mat) -> Self {
+                let array: [[$type; $rows]; $cols] = value.into();
+                array.into()
+            }
+        }
+
+        impl Uniform for $column<$type> {
+            type Align = <$mat as Uniform>::Align;
+            type Std140 = <$mat as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+
+        impl From<$row<$type>> for $mat {
+            fn from(value: $row<$type>) -> Self {
+                $column::from(value).into()
+            }
+        }
+
+        impl From<$mat> for $row<$type> {
+            fn from(value: $mat) -> Self {
+                $column::from(value).into()
+            }
+        }
+
+        impl Uniform for $row<$type> {
+            type Align = <$mat as Uniform>::Align;
+            type Std140 = <$mat as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
+impl_vec_from_mint!(ivec2 : Vector2 => [int;    2]);
+impl_vec_from_mint!(ivec3 : Vector3 => [int;    3]);
+impl_vec_from_mint!(ivec4 : Vector4 => [int;    4]);
+impl_vec_from_mint!(uvec2 : Vector2 => [uint;   2]);
+impl_vec_from_mint!(uvec3 : Vector3 => [uint;   3]);
+impl_vec_from_mint!(uvec4 : Vector4 => [uint;   4]);
+impl_vec_from_mint!( vec2 : Vector2 => [float;  2]);
+impl_vec_from_mint!( vec3 : Vector3 => [float;  3]);
+impl_vec_from_mint!( vec4 : Vector4 => [float;  4]);
+impl_vec_from_mint!(dvec2 : Vector2 => [double; 2]);
+impl_vec_from_mint!(dvec3 : Vector3 => [double; 3]);
+impl_vec_from_mint!(dvec4 : Vector4 => [double; 4]);
+
+impl_vec_from_mint!(ivec2 : Point2 => [int;    2]);
+impl_vec_from_mint!(ivec3 : Point3 => [int;    3]);
+impl_vec_from_mint!(uvec2 : Point2 => [uint;   2]);
+impl_vec_from_mint!(uvec3 : Point3 => [uint;   3]);
+impl_vec_from_mint!( vec2 : Point2 => [float;  2]);
+impl_vec_from_mint!( vec3 : Point3 => [float;  3]);
+impl_vec_from_mint!(dvec2 : Point2 => [double; 2]);
+impl_vec_from_mint!(dvec3 : Point3 => [double; 3]);
+
+impl_vec_from_mint!( vec4 : Quaternion => [float;  4]);
+impl_vec_from_mint!(dvec4 : Quaternion => [double; 4]);
+
+impl_mat_from_mint!(imat2x2 : ColumnMatrix2, RowMatrix2 => [[int; 2]; 2]);
+impl_mat_from_mint!(imat2x3 : ColumnMatrix3x2, RowMatrix3x2 => [[int; 3]; 2]);
+impl_mat_from_mint!(imat2x4 : ColumnMatrix4x2, RowMatrix4x2 => [[int; 4]; 2]);
+impl_mat_from_mint!(imat3x2 : ColumnMatrix2x3, RowMatrix2x3 => [[int; 2]; 3]);
+impl_mat_from_mint!(imat3x3 : ColumnMatrix3, RowMatrix3 => [[int; 3]; 3]);
+impl_mat_from_mint!(imat3x4 : ColumnMatrix4x3, RowMatrix4x3 => [[int; 4]; 3]);
+impl_mat_from_mint!(imat4x2 : ColumnMatrix2x4, RowMatrix2x4 => [[int; 2]; 4]);
+impl_mat_from_mint!(imat4x3 : ColumnMatrix3x4, RowMatrix3x4 => [[int; 3]; 4]);
+impl_mat_from_mint!(imat4x4 : ColumnMatrix4, RowMatrix4 => [[int; 4]; 4]);
+impl_mat_from_mint!(umat2x2 : ColumnMatrix2, RowMatrix2 => [[uint; 2]; 2]);
+impl_mat_from_mint!(umat2x3 : ColumnMatrix3x2, RowMatrix3x2 => [[uint; 3]; 2]);
+impl_mat_from_mint!(umat2x4 : ColumnMatrix4x2, RowMatrix4x2 => [[uint; 4]; 2]);
+impl_mat_from_mint!(umat3x2 : ColumnMatrix2x3, RowMatrix2x3 => [[uint; 2]; 3]);
+impl_mat_from_mint!(umat3x3 : ColumnMatrix3, RowMatrix3 => [[uint; 3]; 3]);
+impl_mat_from_mint!(umat3x4 : ColumnMatrix4x3, RowMatrix4x3 => [[uint; 4]; 3]);
+impl_mat_from_mint!(umat4x2 : ColumnMatrix2x4, RowMatrix2x4 => [[uint; 2]; 4]);
+impl_mat_from_mint!(umat4x3 : ColumnMatrix3x4, RowMatrix3x4 => [[uint; 3]; 4]);
+impl_mat_from_mint!(umat4x4 : ColumnMatrix4, RowMatrix4 => [[uint; 4]; 4]);
+impl_mat_from_mint!( mat2x2 : ColumnMatrix2, RowMatrix2 => [[float; 2]; 2]);
+impl_mat_from_mint!( mat2x3 : ColumnMatrix3x2, RowMatrix3x2 => [[float; 3]; 2]);
+impl_mat_from_mint!( mat2x4 : ColumnMatrix4x2, RowMatrix4x2 => [[float; 4]; 2]);
+impl_mat_from_mint!( mat3x2 : ColumnMatrix2x3, RowMatrix2x3 => [[float; 2]; 3]);
+impl_mat_from_mint!( mat3x3 : ColumnMatrix3, RowMatrix3 => [[float; 3]; 3]);
+impl_mat_from_mint!( mat3x4 : ColumnMatrix4x3, RowMatrix4x3 => [[float; 4]; 3]);
+impl_mat_from_mint!( mat4x2 : ColumnMatrix2x4, RowMatrix2x4 => [[float; 2]; 4]);
+impl_mat_from_mint!( mat4x3 : ColumnMatrix3x4, RowMatrix3x4 => [[float; 3]; 4]);
+impl_mat_from_mint!( mat4x4 : ColumnMatrix4, RowMatrix4 => [[float; 4]; 4]);
+impl_mat_from_mint!(dmat2x2 : ColumnMatrix2, RowMatrix2 => [[double; 2]; 2]);
+impl_mat_from_mint!(dmat2x3 : ColumnMatrix3x2, RowMatrix3x2 => [[double; 3]; 2]);
+impl_mat_from_mint!(dmat2x4 : ColumnMatrix4x2, RowMatrix4x2 => [[double; 4]; 2]);
+impl_mat_from_mint!(dmat3x2 : ColumnMatrix2x3, RowMatrix2x3 => [[double; 2]; 3]);
+impl_mat_from_mint!(dmat3x3 : ColumnMatrix3, RowMatrix3 => [[double; 3]; 3]);
+impl_mat_from_mint!(dmat3x4 : ColumnMatrix4x3, RowMatrix4x3 => [[double; 4]; 3]);
+impl_mat_from_mint!(dmat4x2 : ColumnMatrix2x4, RowMatrix2x4 => [[double; 2]; 4]);
+impl_mat_from_mint!(dmat4x3 : ColumnMatrix3x4, RowMatrix3x4 => [[double; 3]; 4]);
+impl_mat_from_mint!(dmat4x4 : ColumnMatrix4, RowMatrix4 => [[double; 4]; 4]);
+
+#[test]
+fn test_mint() {
+    let v3 = Vector3::from([1.0f32, 2.0, 3.0]);
+    assert_eq!(v3.std140(), vec3::from([1.0, 2.0, 3.0]));
+    assert_eq!(Vector3::<f32>::from(vec3::from(v3)), v3);
+
+    let p2 = Point2::from([1i32, 2]);
+    assert_eq!(p2.std140(), ivec2::from([1, 2]));
+
+    let q = Quaternion::from([0.0f64, 0.6, 0.0, 0.8]);
+    assert_eq!(q.std140(), dvec4::from([0.0, 0.6, 0.0, 0.8]));
+    assert_eq!(Quaternion::<f64>::from(dvec4::from(q)), q);
+
+    // Two rows and three columns.
+    let column = ColumnMatrix2x3::from([[1.0f32, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+    let row = RowMatrix2x3::from([[1.0f32, 3.0, 5.0], [2.0, 4.0, 6.0]]);
+    assert_eq!(
+        column.std140(),
+        mat3x2::from([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]])
+    );
+    assert_eq!(row.std140(), column.std140());
+    assert_eq!(ColumnMatrix2x3::<f32>::from(mat3x2::from(column)), column);
+    assert_eq!(RowMatrix2x3::<f32>::from(mat3x2::from(row)), row);
+}
@@ -0,0 +1,154 @@
+//! Metal Shading Language layout.
+//!
+//! MSL lays out structures as C does.
+//! `float3` is aligned and padded to 16 bytes while `packed_float3` occupies 12 bytes
+//! and is aligned as `float`. Members marked with `#[glsl(packed)]` use packed vectors.
+//! MSL has no `double` type, and its `bool` is a single byte, so booleans are declared as `uint`.
+
+use crate::layout::{Layout, Msl};
+use crate::reflect::{declare_members, MemberInfo, ScalarType, TypeInfo};
+use crate::uniform::Uniform;
+
+/// Size of `T` laid out as MSL structure.
+pub const fn size_of<T: Uniform>() -> usize {
+    <T as Layout<Msl>>::SIZE
+}
+
+/// Alignment of `T` laid out as MSL structure.
+pub const fn align_of<T: Uniform>() -> usize {
+    <T as Layout<Msl>>::ALIGN
+}
+
+/// Write `value` laid out as MSL structure into `out`.
+/// Padding bytes are zeroed.
+///
+/// # Panics
+///
+/// If `out` is shorter than `size_of::<T>()`.
+pub fn write<T: Uniform>(value: &T, out: &mut [u8]) {
+    Layout::<Msl>::write_layout(value, out)
+}
+
+/// Get `value` laid out as MSL structure.
+pub fn to_bytes<T: Uniform>(value: &T) -> Vec<u8> {
+    Layout::<Msl>::to_bytes(value)
+}
+
+/// Generate MSL declarations of structure `T` and structures it uses.
+///
+/// # Panics
+///
+/// If `T` is not a structure, contains `double` values
+/// or different structures it uses have the same name.
+pub fn declaration<T: Uniform>() -> String {
+    if !matches!(T::TYPE_INFO, TypeInfo::Struct(_)) {
+        panic!("Only structures can be declared");
+    }
+
+    let mut source = String::new();
+    for (index, info) in T::TYPE_INFO.structs().into_iter().enumerate() {
+        if index > 0 {
+            source.push('\n');
+        }
+        source.push_str("struct ");
+        source.push_str(info.name);
+        declare_members(&mut source, info, type_name);
+    }
+    source
+}
+
+fn scalar_name(scalar: ScalarType) -> &'static str {
+    match scalar {
+        ScalarType::Bool | ScalarType::Uint => "uint",
+        ScalarType::Int => "int",
+        ScalarType::Float => "float",
+        ScalarType::Double => panic!("MSL does not support `double`"),
+    }
+}
+
+fn type_name(ty: &TypeInfo, member: &MemberInfo) -> String {
+    match *ty {
+        TypeInfo::Scalar(scalar) => scalar_name(scalar).to_owned(),
+        TypeInfo::Vector(scalar, len) if member.packed => {
+            format!("packed_{}{}", scalar_name(scalar), len)
+        }
+        TypeInfo::Vector(scalar, len) => format!("{}{}", scalar_name(scalar), len),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => format!("{}{}x{}", scalar_name(scalar), columns, rows),
+        TypeInfo::Array(..) => unreachable!("Arrays are declared by their elements"),
+        TypeInfo::Struct(info) => info.name.to_owned(),
+    }
+}
+
+#[test]
+fn test_msl() {
+    use crate as glsl_layout;
+    use crate::{bvec2, float, mat3, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Light {
+        #[glsl(packed)]
+        position: vec3,
+        intensity: float,
+        color: vec3,
+        transform: mat3,
+        weights: [float; 3],
+        flags: bvec2,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Scene {
+        light: Light,
+        scale: float,
+    }
+
+    // position: 0, intensity: 12, color: 16, transform: 32, weights: 80, flags: 96.
+    assert_eq!(size_of::<Light>(), 112);
+    assert_eq!(align_of::<Light>(), 16);
+    assert_eq!(size_of::<Scene>(), 128);
+
+    let scene = Scene {
+        light: Light {
+            position: [1.0, 2.0, 3.0].into(),
+            intensity: 4.0,
+            transform: [[5.0, 0.0, 0.0], [6.0, 0.0, 0.0], [7.0, 0.0, 0.0]].into(),
+            weights: [8.0, 9.0, 10.0],
+            ..Default::default()
+        },
+        scale: 11.0,
+    };
+    let bytes = to_bytes(&scene);
+    let float_at = |offset: usize| {
+        let mut raw = [0; 4];
+        raw.copy_from_slice(&bytes[offset..offset + 4]);
+        f32::from_ne_bytes(raw)
+    };
+    assert_eq!(float_at(8), 3.0);
+    assert_eq!(float_at(12), 4.0);
+    assert_eq!(float_at(48), 6.0);
+    assert_eq!(float_at(84), 9.0);
+    assert_eq!(float_at(112), 11.0);
+
+    assert_eq!(
+        declaration::<Scene>(),
+        "struct Light
+{
+    packed_float3 position;
+    float intensity;
+    float3 color;
+    float3x3 transform;
+    float weights[3];
+    uint2 flags;
+};
+
+struct Scene
+{
+    Light light;
+    float scale;
+};
+"
+    );
+}
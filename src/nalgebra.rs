@@ -1,8 +1,17 @@
-use crate::mat::{dmat2, dmat3, dmat4, imat2, imat3, imat4, mat2, mat3, mat4, umat2, umat3, umat4};
+use crate::mat::{
+    dmat2x2, dmat2x3, dmat2x4, dmat3x2, dmat3x3, dmat3x4, dmat4, dmat4x2, dmat4x3, dmat4x4,
+    imat2x2, imat2x3, imat2x4, imat3x2, imat3x3, imat3x4, imat4x2, imat4x3, imat4x4, mat2x2,
+    mat2x3, mat2x4, mat3x2, mat3x3, mat3x4, mat4, mat4x2, mat4x3, mat4x4, umat2x2, umat2x3,
+    umat2x4, umat3x2, umat3x3, umat3x4, umat4x2, umat4x3, umat4x4,
+};
+use crate::reflect::TypeInfo;
 use crate::scalar::{double, float, int, uint};
 use crate::uniform::Uniform;
 use crate::vec::{dvec2, dvec3, dvec4, ivec2, ivec3, ivec4, uvec2, uvec3, uvec4, vec2, vec3, vec4};
-use nalgebra::{Matrix2, Matrix3, Matrix4, Vector2, Vector3, Vector4};
+use nalgebra::{
+    Isometry3, Point2, Point3, Quaternion, SMatrix, Similarity3, Unit, UnitQuaternion, Vector2,
+    Vector3, Vector4,
+};
 
 macro_rules! impl_vec_from_nalgebra {
     ($vec:ident : $nalgebra:ident => [$type:ty; $size:tt]) => {
@@ -13,9 +22,17 @@ macro_rules! impl_vec_from_nalgebra {
             }
         }
 
+        impl From<$vec> for $nalgebra<$type> {
+            fn from(value: $vec) -> Self {
+                let array: [$type; $size] = value.into();
+                array.into()
+            }
+        }
+
         impl Uniform for $nalgebra<$type> {
             type Align = <$vec as Uniform>::Align;
             type Std140 = <$vec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
 
             fn std140(&self) -> Self::Std140 {
                 Self::Std140::from(*self)
@@ -25,21 +42,111 @@ macro_rules! impl_vec_from_nalgebra {
 }
 
 macro_rules! impl_mat_from_nalgebra {
-    ($mat:ident : $nalgebra:ident => [$type:ty; $size:tt]) => {
+    ($mat:ident => [[$type:ty; $rows:tt]; $cols:tt]) => {
+        impl From<SMatrix<$type, $rows, $cols>> for $mat {
+            fn from(value: SMatrix<$type, $rows, $cols>) -> Self {
+                let array: [[$type; $rows]; $cols] = value.into();
+                array.into()
+            }
+        }
+
+        impl From<$mat> for SMatrix<$type, $rows, $cols> {
+            fn from(value: $mat) -> Self {
+                let array: [[$type; $rows]; $cols] = value.into();
+                array.into()
+            }
+        }
+
+        impl Uniform for SMatrix<$type, $rows, $cols> {
+            type Align = <$mat as Uniform>::Align;
+            type Std140 = <$mat as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                let array: [[$type; $rows]; $cols] = (*self).into();
+                Self::Std140::from(array)
+            }
+        }
+    };
+}
+
+macro_rules! impl_quat_from_nalgebra {
+    ($vec:ident => $type:ty) => {
+        impl From<Quaternion<$type>> for $vec {
+            fn from(value: Quaternion<$type>) -> Self {
+                value.coords.into()
+            }
+        }
+
+        impl From<$vec> for Quaternion<$type> {
+            fn from(value: $vec) -> Self {
+                Vector4::<$type>::from(value).into()
+            }
+        }
+
+        impl Uniform for Quaternion<$type> {
+            type Align = <$vec as Uniform>::Align;
+            type Std140 = <$vec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+
+        impl From<UnitQuaternion<$type>> for $vec {
+            fn from(value: UnitQuaternion<$type>) -> Self {
+                value.into_inner().into()
+            }
+        }
+
+        impl Uniform for UnitQuaternion<$type> {
+            type Align = <$vec as Uniform>::Align;
+            type Std140 = <$vec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_unit_from_nalgebra {
+    ($vec:ident : $nalgebra:ident => $type:ty) => {
+        impl From<Unit<$nalgebra<$type>>> for $vec {
+            fn from(value: Unit<$nalgebra<$type>>) -> Self {
+                value.into_inner().into()
+            }
+        }
+
+        impl Uniform for Unit<$nalgebra<$type>> {
+            type Align = <$vec as Uniform>::Align;
+            type Std140 = <$vec as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$vec as Uniform>::TYPE_INFO;
+
+            fn std140(&self) -> Self::Std140 {
+                Self::Std140::from(*self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_transform_from_nalgebra {
+    ($mat:ident : $nalgebra:ident => $type:ty) => {
         impl From<$nalgebra<$type>> for $mat {
             fn from(value: $nalgebra<$type>) -> Self {
-                let array: [[$type; $size]; $size] = value.into();
-                array.into()
+                value.to_homogeneous().into()
             }
         }
 
         impl Uniform for $nalgebra<$type> {
             type Align = <$mat as Uniform>::Align;
             type Std140 = <$mat as Uniform>::Std140;
+            const TYPE_INFO: TypeInfo = <$mat as Uniform>::TYPE_INFO;
 
             fn std140(&self) -> Self::Std140 {
-                let array: [[$type; $size]; $size] = (*self).into();
-                Self::Std140::from(array)
+                Self::Std140::from(*self)
             }
         }
     };
@@ -58,23 +165,70 @@ impl_vec_from_nalgebra!(dvec2 : Vector2 => [double; 2]);
 impl_vec_from_nalgebra!(dvec3 : Vector3 => [double; 3]);
 impl_vec_from_nalgebra!(dvec4 : Vector4 => [double; 4]);
 
-impl_mat_from_nalgebra!(imat2 : Matrix2 => [int;    2]);
-impl_mat_from_nalgebra!(imat3 : Matrix3 => [int;    3]);
-impl_mat_from_nalgebra!(imat4 : Matrix4 => [int;    4]);
-impl_mat_from_nalgebra!(umat2 : Matrix2 => [uint;   2]);
-impl_mat_from_nalgebra!(umat3 : Matrix3 => [uint;   3]);
-impl_mat_from_nalgebra!(umat4 : Matrix4 => [uint;   4]);
-impl_mat_from_nalgebra!( mat2 : Matrix2 => [float;  2]);
-impl_mat_from_nalgebra!( mat3 : Matrix3 => [float;  3]);
-impl_mat_from_nalgebra!( mat4 : Matrix4 => [float;  4]);
-impl_mat_from_nalgebra!(dmat2 : Matrix2 => [double; 2]);
-impl_mat_from_nalgebra!(dmat3 : Matrix3 => [double; 3]);
-impl_mat_from_nalgebra!(dmat4 : Matrix4 => [double; 4]);
+impl_vec_from_nalgebra!(ivec2 : Point2 => [int;    2]);
+impl_vec_from_nalgebra!(ivec3 : Point3 => [int;    3]);
+impl_vec_from_nalgebra!(uvec2 : Point2 => [uint;   2]);
+impl_vec_from_nalgebra!(uvec3 : Point3 => [uint;   3]);
+impl_vec_from_nalgebra!( vec2 : Point2 => [float;  2]);
+impl_vec_from_nalgebra!( vec3 : Point3 => [float;  3]);
+impl_vec_from_nalgebra!(dvec2 : Point2 => [double; 2]);
+impl_vec_from_nalgebra!(dvec3 : Point3 => [double; 3]);
+
+impl_mat_from_nalgebra!(imat2x2 => [[int; 2]; 2]);
+impl_mat_from_nalgebra!(imat2x3 => [[int; 3]; 2]);
+impl_mat_from_nalgebra!(imat2x4 => [[int; 4]; 2]);
+impl_mat_from_nalgebra!(imat3x2 => [[int; 2]; 3]);
+impl_mat_from_nalgebra!(imat3x3 => [[int; 3]; 3]);
+impl_mat_from_nalgebra!(imat3x4 => [[int; 4]; 3]);
+impl_mat_from_nalgebra!(imat4x2 => [[int; 2]; 4]);
+impl_mat_from_nalgebra!(imat4x3 => [[int; 3]; 4]);
+impl_mat_from_nalgebra!(imat4x4 => [[int; 4]; 4]);
+impl_mat_from_nalgebra!(umat2x2 => [[uint; 2]; 2]);
+impl_mat_from_nalgebra!(umat2x3 => [[uint; 3]; 2]);
+impl_mat_from_nalgebra!(umat2x4 => [[uint; 4]; 2]);
+impl_mat_from_nalgebra!(umat3x2 => [[uint; 2]; 3]);
+impl_mat_from_nalgebra!(umat3x3 => [[uint; 3]; 3]);
+impl_mat_from_nalgebra!(umat3x4 => [[uint; 4]; 3]);
+impl_mat_from_nalgebra!(umat4x2 => [[uint; 2]; 4]);
+impl_mat_from_nalgebra!(umat4x3 => [[uint; 3]; 4]);
+impl_mat_from_nalgebra!(umat4x4 => [[uint; 4]; 4]);
+impl_mat_from_nalgebra!( mat2x2 => [[float; 2]; 2]);
+impl_mat_from_nalgebra!( mat2x3 => [[float; 3]; 2]);
+impl_mat_from_nalgebra!( mat2x4 => [[float; 4]; 2]);
+impl_mat_from_nalgebra!( mat3x2 => [[float; 2]; 3]);
+impl_mat_from_nalgebra!( mat3x3 => [[float; 3]; 3]);
+impl_mat_from_nalgebra!( mat3x4 => [[float; 4]; 3]);
+impl_mat_from_nalgebra!( mat4x2 => [[float; 2]; 4]);
+impl_mat_from_nalgebra!( mat4x3 => [[float; 3]; 4]);
+impl_mat_from_nalgebra!( mat4x4 => [[float; 4]; 4]);
+impl_mat_from_nalgebra!(dmat2x2 => [[double; 2]; 2]);
+impl_mat_from_nalgebra!(dmat2x3 => [[double; 3]; 2]);
+impl_mat_from_nalgebra!(dmat2x4 => [[double; 4]; 2]);
+impl_mat_from_nalgebra!(dmat3x2 => [[double; 2]; 3]);
+impl_mat_from_nalgebra!(dmat3x3 => [[double; 3]; 3]);
+impl_mat_from_nalgebra!(dmat3x4 => [[double; 4]; 3]);
+impl_mat_from_nalgebra!(dmat4x2 => [[double; 2]; 4]);
+impl_mat_from_nalgebra!(dmat4x3 => [[double; 3]; 4]);
+impl_mat_from_nalgebra!(dmat4x4 => [[double; 4]; 4]);
+
+impl_quat_from_nalgebra!( vec4 => float);
+impl_quat_from_nalgebra!(dvec4 => double);
+
+impl_unit_from_nalgebra!( vec2 : Vector2 => float);
+impl_unit_from_nalgebra!( vec3 : Vector3 => float);
+impl_unit_from_nalgebra!( vec4 : Vector4 => float);
+impl_unit_from_nalgebra!(dvec2 : Vector2 => double);
+impl_unit_from_nalgebra!(dvec3 : Vector3 => double);
+impl_unit_from_nalgebra!(dvec4 : Vector4 => double);
+
+impl_transform_from_nalgebra!( mat4 : Isometry3 => float);
+impl_transform_from_nalgebra!( mat4 : Similarity3 => float);
+impl_transform_from_nalgebra!(dmat4 : Isometry3 => double);
+impl_transform_from_nalgebra!(dmat4 : Similarity3 => double);
 
 #[test]
 fn test_nalgebra() {
-    use crate::uniform::Std140;
-
+    use crate::mat::mat3;
     let v3: vec3 = [1.0f32, 2.0, 3.0].into();
     let gv3_to_v3: vec3 = Vector3::new(1.0f32, 2.0, 3.0).into();
     let gv3 = Vector3::new(1.0f32, 2.0, 3.0);
@@ -82,8 +236,55 @@ fn test_nalgebra() {
     assert_eq!(gv3.std140(), gv3_to_v3.std140());
 
     let m3: mat3 = [[1.0f32, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]].into();
-    let gm3_to_m3: mat3 = Matrix3::<f32>::identity().into();
-    let gm3 = Matrix3::<f32>::identity();
+    let gm3_to_m3: mat3 = nalgebra::Matrix3::<f32>::identity().into();
+    let gm3 = nalgebra::Matrix3::<f32>::identity();
     assert_eq!(m3.std140(), gm3.std140());
     assert_eq!(gm3.std140(), gm3_to_m3.std140());
+
+    assert_eq!(Vector3::<f32>::from(v3), gv3);
+    assert_eq!(
+        Vector2::<i32>::from(ivec2::from([1, 2])),
+        Vector2::new(1, 2)
+    );
+    assert_eq!(nalgebra::Matrix3::<f32>::from(m3), gm3);
+
+    let gm4 = nalgebra::Matrix4::<f64>::new(
+        1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0, 16.0,
+    );
+    assert_eq!(nalgebra::Matrix4::<f64>::from(dmat4::from(gm4)), gm4);
+}
+
+#[test]
+fn test_nalgebra_extended() {
+    use nalgebra::{Matrix2x4, Matrix4x3, Translation3};
+
+    // 4 rows and 3 columns is `mat3x4` in glsl.
+    let m = Matrix4x3::<f32>::from_fn(|r, c| (r * 3 + c) as f32);
+    let cols: [[f32; 4]; 3] = mat3x4::from(m).into();
+    assert_eq!(cols, <[[f32; 4]; 3]>::from(m));
+    assert_eq!(m.std140(), mat3x4::from(m));
+    assert_eq!(Matrix4x3::<f32>::from(mat3x4::from(m)), m);
+
+    let m = Matrix2x4::<i32>::from_fn(|r, c| (r * 4 + c) as i32);
+    assert_eq!(Matrix2x4::<i32>::from(m.std140()), m);
+
+    let p = Point3::new(1.0f32, 2.0, 3.0);
+    assert_eq!(p.std140(), vec3::from([1.0, 2.0, 3.0]));
+    assert_eq!(Point3::<f32>::from(vec3::from(p)), p);
+
+    let q = Quaternion::new(0.8f32, 0.0, 0.6, 0.0);
+    assert_eq!(q.std140(), vec4::from([0.0, 0.6, 0.0, 0.8]));
+    assert_eq!(Quaternion::<f32>::from(vec4::from(q)), q);
+    let uq = UnitQuaternion::from_quaternion(q);
+    assert_eq!(uq.std140(), q.std140());
+
+    let axis = Vector3::<f64>::y_axis();
+    assert_eq!(axis.std140(), dvec3::from([0.0, 1.0, 0.0]));
+
+    let iso = Isometry3::from_parts(Translation3::new(1.0f32, 2.0, 3.0), uq);
+    assert_eq!(iso.std140(), mat4::from(iso.to_homogeneous()));
+    let sim = Similarity3::from_isometry(iso, 2.0);
+    assert_eq!(sim.std140(), mat4::from(sim.to_homogeneous()));
+    let iso = Isometry3::<f64>::translation(1.0, 2.0, 3.0);
+    assert_eq!(iso.std140(), dmat4::from(iso.to_homogeneous()));
 }
@@ -0,0 +1,393 @@
+use crate::align::{Align16, OptionPadding};
+use crate::reflect::{MemberInfo, StructInfo, StructName, TypeInfo};
+use crate::scalar::boolean;
+use crate::uniform::{Std140, Uniform};
+use std::{
+    fmt,
+    mem::{offset_of, size_of, MaybeUninit},
+};
+
+/// Access to flag and value of `Option` for `derive(Uniform)`.
+#[doc(hidden)]
+pub trait Optional {
+    type Value: Uniform;
+
+    fn flag(&self) -> boolean;
+
+    fn value_std140(&self) -> <Self::Value as Uniform>::Std140;
+
+    /// Write the value in `std140` layout, or zeroes if it is absent.
+    fn write_value(&self, out: &mut [u8]);
+}
+
+impl<T> Optional for Option<T>
+where
+    T: Uniform,
+{
+    type Value = T;
+
+    fn flag(&self) -> boolean {
+        self.is_some().into()
+    }
+
+    fn value_std140(&self) -> T::Std140 {
+        match self {
+            Some(value) => value.std140(),
+            // Any bit pattern is valid for `Std140` types.
+            None => unsafe { MaybeUninit::zeroed().assume_init() },
+        }
+    }
+
+    fn write_value(&self, out: &mut [u8]) {
+        match self {
+            Some(value) => value.write_std140(out),
+            None => out[..T::STD140_SIZE].fill(0),
+        }
+    }
+}
+
+/// `std140` representation of `Option`.
+/// Optional value is laid out as `bool present` followed by the value, zeroed when absent.
+///
+/// On its own it is declared as structure with members `present` and `value`,
+/// named after the value type, i.e. `Option_float`.
+/// Members of derived structures can be flattened instead with `#[glsl(flag = "name")]`
+/// attribute that gives name to the flag.
+///
+/// Padding after the flag and the value is explicit, so values with padding that depends on their size,
+/// like `vec3`, can't be held on their own and have to be flattened.
+///
+/// ```
+/// # use glsl_layout::*;
+/// #[derive(Clone, Copy, Default, Uniform)]
+/// struct Fog {
+///     color: vec3,
+///     density: float,
+/// }
+///
+/// #[derive(Clone, Copy, Default, Uniform)]
+/// struct Scene {
+///     #[glsl(flag = "has_fog")]
+///     fog: Option<Fog>,
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use glsl_layout::*;
+/// #[derive(Clone, Copy, Default, Uniform)]
+/// struct Light {
+///     direction: Option<vec3>,
+/// }
+///
+/// let _ = Light::default().std140();
+/// ```
+#[repr(C, align(16))]
+pub struct LayoutStd140Option<T: Uniform>
+where
+    T::Align: OptionPadding,
+{
+    present: boolean,
+    _pad: <T::Align as OptionPadding>::Head,
+    _align: T::Align,
+    value: T::Std140,
+    _tail: <T::Align as OptionPadding>::Tail,
+}
+
+impl<T> LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+    const NAME: StructName = StructName::new("Option").with_type(&T::TYPE_INFO);
+
+    const PADDED: () = assert!(
+        offset_of!(Self, value) == size_of::<boolean>() + size_of::<<T::Align as OptionPadding>::Head>()
+            && size_of::<Self>()
+                == offset_of!(Self, _tail) + size_of::<<T::Align as OptionPadding>::Tail>(),
+        "`Option` can't hold value with padding that depends on its size, flatten it with `#[glsl(flag = \"...\")]`"
+    );
+
+    /// Check if value is present.
+    pub fn is_some(&self) -> bool {
+        self.present.into()
+    }
+
+    /// Get value if present.
+    pub fn get(&self) -> Option<T::Std140> {
+        if self.is_some() {
+            Some(self.value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Clone for LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+}
+
+impl<T> Default for LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+    fn default() -> Self {
+        None::<T>.std140()
+    }
+}
+
+impl<T> fmt::Debug for LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+    T::Std140: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LayoutStd140Option")
+            .field("present", &self.present)
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+// Flag and value are padded explicitly and the value has no padding bytes.
+unsafe impl<T> Std140 for LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+}
+
+impl<T> Uniform for LayoutStd140Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+    type Align = Align16;
+    type Std140 = Self;
+    const TYPE_INFO: TypeInfo = <Option<T> as Uniform>::TYPE_INFO;
+
+    fn std140(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Uniform for Option<T>
+where
+    T: Uniform,
+    T::Align: OptionPadding,
+{
+    type Align = Align16;
+    type Std140 = LayoutStd140Option<T>;
+    const TYPE_INFO: TypeInfo = {
+        let () = LayoutStd140Option::<T>::PADDED;
+        TypeInfo::Struct(&StructInfo::new(
+            LayoutStd140Option::<T>::NAME.as_str(),
+            &[
+                MemberInfo::new("present", &<boolean as Uniform>::TYPE_INFO),
+                MemberInfo::new("value", &T::TYPE_INFO),
+            ],
+        ))
+    };
+
+    fn std140(&self) -> LayoutStd140Option<T> {
+        let () = LayoutStd140Option::<T>::PADDED;
+        LayoutStd140Option {
+            present: self.flag(),
+            _pad: Default::default(),
+            _align: Default::default(),
+            value: self.value_std140(),
+            _tail: Default::default(),
+        }
+    }
+
+    fn write_std140(&self, out: &mut [u8]) {
+        let () = LayoutStd140Option::<T>::PADDED;
+        let out = &mut out[..Self::STD140_SIZE];
+        out.fill(0);
+        self.flag().write_std140(out);
+        if let Some(value) = self {
+            value.write_std140(&mut out[offset_of!(LayoutStd140Option<T>, value)..]);
+        }
+    }
+}
+
+#[test]
+fn test_option() {
+    use crate as glsl_layout;
+    use crate::{float, glsl, vec3};
+
+    #[derive(Clone, Copy, Debug, Default, Uniform)]
+    struct Fog {
+        color: vec3,
+        density: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Scene {
+        #[glsl(flag = "has_fog")]
+        fog: Option<Fog>,
+        exposure: float,
+        gamma: Option<float>,
+    }
+
+    let scene = Scene {
+        fog: Some(Fog {
+            color: [1.0, 2.0, 3.0].into(),
+            density: 4.0,
+        }),
+        exposure: 5.0,
+        gamma: Some(6.0),
+    };
+
+    // has_fog: 0, fog: 16, exposure: 32, gamma: 48 with value at 52.
+    assert_eq!(Scene::STD140_SIZE, 64);
+    let mut bytes = vec![0xff; Scene::STD140_SIZE];
+    scene.write_std140(&mut bytes);
+    assert_eq!(&bytes[0..4], &1u32.to_ne_bytes());
+    assert_eq!(&bytes[28..32], &4.0f32.to_ne_bytes());
+    assert_eq!(&bytes[48..52], &1u32.to_ne_bytes());
+    assert_eq!(&bytes[52..56], &6.0f32.to_ne_bytes());
+    assert!(bytes[56..].iter().all(|&byte| byte == 0));
+
+    let std140 = Scene {
+        fog: None,
+        gamma: None,
+        ..scene
+    }
+    .std140();
+    assert!(!bool::from(std140.has_fog));
+    assert_eq!(std140.fog.density, 0.0);
+    assert!(std140.gamma.get().is_none());
+    assert_eq!(scene.std140().gamma.get(), Some(6.0));
+
+    assert_eq!(
+        glsl::declaration::<Scene>(),
+        "struct Fog
+{
+    vec3 color;
+    float density;
+};
+
+struct Option_float
+{
+    bool present;
+    float value;
+};
+
+struct Scene
+{
+    bool has_fog;
+    Fog fog;
+    float exposure;
+    Option_float gamma;
+};
+"
+    );
+    // Padding inside flattened value is zeroed rather than copied from a temporary.
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Haze {
+        density: float,
+        color: vec3,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Weather {
+        #[glsl(flag = "has_haze")]
+        haze: Option<Haze>,
+    }
+
+    // has_haze: 0, haze: 16 with density at 16 and color at 32.
+    assert_eq!(Weather::STD140_SIZE, 48);
+    let haze = Haze {
+        density: 1.0,
+        color: [2.0, 3.0, 4.0].into(),
+    };
+    for weather in [Weather { haze: Some(haze) }, Weather { haze: None }] {
+        let mut bytes = vec![0xff; Weather::STD140_SIZE];
+        weather.write_std140(&mut bytes);
+        let mut std140_bytes = vec![0xff; Weather::STD140_SIZE];
+        weather.std140().write_std140(&mut std140_bytes);
+        assert_eq!(bytes, std140_bytes);
+
+        assert!(bytes[4..16].iter().all(|&byte| byte == 0));
+        assert!(bytes[20..32].iter().all(|&byte| byte == 0));
+        assert!(bytes[44..48].iter().all(|&byte| byte == 0));
+        let expected = match weather.haze {
+            Some(_) => (1u32, 1.0f32, 4.0f32),
+            None => (0, 0.0, 0.0),
+        };
+        assert_eq!(&bytes[0..4], &expected.0.to_ne_bytes());
+        assert_eq!(&bytes[16..20], &expected.1.to_ne_bytes());
+        assert_eq!(&bytes[40..44], &expected.2.to_ne_bytes());
+    }
+
+    // Different payloads are declared as distinct structures.
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Settings {
+        gamma: Option<float>,
+        fog: Option<Fog>,
+    }
+
+    assert_eq!(
+        glsl::declaration::<Settings>(),
+        "struct Option_float
+{
+    bool present;
+    float value;
+};
+
+struct Fog
+{
+    vec3 color;
+    float density;
+};
+
+struct Option_Fog
+{
+    bool present;
+    Fog value;
+};
+
+struct Settings
+{
+    Option_float gamma;
+    Option_Fog fog;
+};
+"
+    );
+
+    // Padding around the value is explicit and zeroed.
+    let settings = Settings {
+        gamma: Some(2.0),
+        fog: Some(Fog {
+            color: [1.0, 2.0, 3.0].into(),
+            density: 4.0,
+        }),
+    }
+    .std140();
+    let raw = settings.gamma.as_raw();
+    assert_eq!(raw.len(), 16);
+    assert_eq!(&raw[4..8], &2.0f32.to_ne_bytes());
+    assert!(raw[8..].iter().all(|&byte| byte == 0));
+    let raw = settings.fog.as_raw();
+    assert_eq!(raw.len(), 32);
+    assert!(raw[4..16].iter().all(|&byte| byte == 0));
+    assert_eq!(&raw[28..32], &4.0f32.to_ne_bytes());
+    assert!(Some(crate::vec2::from([1.0, 2.0])).std140().as_raw()[4..8]
+        .iter()
+        .all(|&byte| byte == 0));
+}
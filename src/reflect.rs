@@ -0,0 +1,361 @@
+/// Scalar types of glsl.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScalarType {
+    /// `bool`. Occupies 4 bytes like `uint`.
+    Bool,
+    /// `int`.
+    Int,
+    /// `uint`.
+    Uint,
+    /// `float`.
+    Float,
+    /// `double`.
+    Double,
+}
+
+impl ScalarType {
+    /// Size of the scalar in bytes.
+    pub const fn size(self) -> usize {
+        match self {
+            ScalarType::Double => 8,
+            _ => 4,
+        }
+    }
+}
+
+/// Description of glsl type that `Uniform` type represents.
+/// Used to compute layouts other than `std140` and to generate shader declarations.
+#[derive(Clone, Copy, Debug)]
+pub enum TypeInfo {
+    /// Scalar value.
+    Scalar(ScalarType),
+
+    /// Vector of 2, 3 or 4 scalars.
+    Vector(ScalarType, usize),
+
+    /// Column-major matrix. Only `float` and `double` matrices exist in glsl.
+    Matrix {
+        /// Type of matrix elements.
+        scalar: ScalarType,
+        /// Number of columns.
+        columns: usize,
+        /// Number of rows. Each column is a vector of this size.
+        rows: usize,
+    },
+
+    /// Fixed-size array.
+    Array(&'static TypeInfo, usize),
+
+    /// Structure.
+    Struct(&'static StructInfo),
+}
+
+impl TypeInfo {
+    /// Matrix of `len` columns of `elem`.
+    /// Matrices of integers and booleans don't exist in glsl, thus they are arrays of columns.
+    pub(crate) const fn array_or_matrix(elem: &'static TypeInfo, len: usize) -> TypeInfo {
+        match *elem {
+            TypeInfo::Vector(scalar @ (ScalarType::Float | ScalarType::Double), rows)
+                if len >= 2 && len <= 4 =>
+            {
+                TypeInfo::Matrix {
+                    scalar,
+                    columns: len,
+                    rows,
+                }
+            }
+            _ => TypeInfo::Array(elem, len),
+        }
+    }
+
+    /// All structures this type consists of, including itself.
+    /// Structures go after structures they depend on.
+    ///
+    /// # Panics
+    ///
+    /// If different structures have the same name.
+    pub fn structs(&self) -> Vec<&'static StructInfo> {
+        fn collect(ty: &TypeInfo, structs: &mut Vec<&'static StructInfo>) {
+            match *ty {
+                TypeInfo::Array(elem, _) => collect(elem, structs),
+                TypeInfo::Struct(info) => {
+                    if info.is_in(structs) {
+                        return;
+                    }
+                    for member in info.members {
+                        collect(member.ty, structs);
+                    }
+                    structs.push(info);
+                }
+                _ => {}
+            }
+        }
+
+        let mut structs = Vec::new();
+        collect(self, &mut structs);
+        structs
+    }
+
+    /// Check if types have the same layout and member names.
+    /// Names of structures are not compared.
+    /// Matrices are the same as arrays of their columns.
+    #[doc(hidden)]
+    pub const fn same(&self, other: &TypeInfo) -> bool {
+        match (*self, *other) {
+            (TypeInfo::Scalar(a), TypeInfo::Scalar(b)) => a as u8 == b as u8,
+            (TypeInfo::Vector(a, n), TypeInfo::Vector(b, m)) => a as u8 == b as u8 && n == m,
+            (
+                TypeInfo::Matrix {
+                    scalar,
+                    columns,
+                    rows,
+                },
+                TypeInfo::Array(elem, len),
+            )
+            | (
+                TypeInfo::Array(elem, len),
+                TypeInfo::Matrix {
+                    scalar,
+                    columns,
+                    rows,
+                },
+            ) => columns == len && TypeInfo::Vector(scalar, rows).same(elem),
+            (
+                TypeInfo::Matrix {
+                    scalar: a,
+                    columns: ac,
+                    rows: ar,
+                },
+                TypeInfo::Matrix {
+                    scalar: b,
+                    columns: bc,
+                    rows: br,
+                },
+            ) => a as u8 == b as u8 && ac == bc && ar == br,
+            (TypeInfo::Array(a, n), TypeInfo::Array(b, m)) => n == m && a.same(b),
+            (TypeInfo::Struct(a), TypeInfo::Struct(b)) => {
+                if a.members.len() != b.members.len() {
+                    return false;
+                }
+                let mut index = 0;
+                while index < a.members.len() {
+                    let (a, b) = (&a.members[index], &b.members[index]);
+                    if !str_eq(a.name, b.name) || !a.ty.same(b.ty) {
+                        return false;
+                    }
+                    index += 1;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Name of generic structure made unique by its parameters, i.e. `BoundedArray_Light_4`.
+pub(crate) struct StructName {
+    bytes: [u8; 128],
+    len: usize,
+}
+
+impl StructName {
+    pub(crate) const fn new(name: &str) -> Self {
+        StructName {
+            bytes: [0; 128],
+            len: 0,
+        }
+        .push(name)
+    }
+
+    /// Append name of the type after underscore.
+    pub(crate) const fn with_type(self, ty: &TypeInfo) -> Self {
+        self.push("_").push_type(ty)
+    }
+
+    /// Append number after underscore.
+    pub(crate) const fn with_len(self, len: usize) -> Self {
+        self.push("_").push_len(len)
+    }
+
+    pub(crate) const fn as_str(&self) -> &str {
+        match std::str::from_utf8(self.bytes.split_at(self.len).0) {
+            Ok(name) => name,
+            Err(_) => unreachable!(),
+        }
+    }
+
+    const fn push(mut self, part: &str) -> Self {
+        let part = part.as_bytes();
+        assert!(
+            self.len + part.len() <= self.bytes.len(),
+            "Structure name is too long"
+        );
+        let mut index = 0;
+        while index < part.len() {
+            self.bytes[self.len] = part[index];
+            self.len += 1;
+            index += 1;
+        }
+        self
+    }
+
+    const fn push_len(mut self, len: usize) -> Self {
+        if len >= 10 {
+            self = self.push_len(len / 10);
+        }
+        let digit = [b'0' + (len % 10) as u8];
+        match std::str::from_utf8(&digit) {
+            Ok(digit) => self.push(digit),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    const fn push_scalar(self, scalar: ScalarType, prefix: bool) -> Self {
+        match (scalar, prefix) {
+            (ScalarType::Bool, false) => self.push("bool"),
+            (ScalarType::Int, false) => self.push("int"),
+            (ScalarType::Uint, false) => self.push("uint"),
+            (ScalarType::Float, false) => self.push("float"),
+            (ScalarType::Double, false) => self.push("double"),
+            (ScalarType::Bool, true) => self.push("b"),
+            (ScalarType::Int, true) => self.push("i"),
+            (ScalarType::Uint, true) => self.push("u"),
+            (ScalarType::Float, true) => self,
+            (ScalarType::Double, true) => self.push("d"),
+        }
+    }
+
+    const fn push_type(self, ty: &TypeInfo) -> Self {
+        match *ty {
+            TypeInfo::Scalar(scalar) => self.push_scalar(scalar, false),
+            TypeInfo::Vector(scalar, len) => {
+                self.push_scalar(scalar, true).push("vec").push_len(len)
+            }
+            TypeInfo::Matrix {
+                scalar,
+                columns,
+                rows,
+            } => self
+                .push_scalar(scalar, true)
+                .push("mat")
+                .push_len(columns)
+                .push("x")
+                .push_len(rows),
+            TypeInfo::Array(elem, len) => self.push_type(elem).with_len(len),
+            TypeInfo::Struct(info) => self.push(info.name),
+        }
+    }
+}
+
+/// Description of glsl structure.
+#[derive(Clone, Copy, Debug)]
+pub struct StructInfo {
+    /// Name of the structure.
+    pub name: &'static str,
+
+    /// Members in declaration order.
+    pub members: &'static [MemberInfo],
+}
+
+impl StructInfo {
+    /// Describe structure with given name and members.
+    pub const fn new(name: &'static str, members: &'static [MemberInfo]) -> Self {
+        StructInfo { name, members }
+    }
+
+    /// Check if structure with the same name is in `structs`.
+    ///
+    /// # Panics
+    ///
+    /// If that structure has different members.
+    pub(crate) fn is_in(&'static self, structs: &[&'static StructInfo]) -> bool {
+        match structs.iter().find(|s| s.name == self.name) {
+            Some(other) => {
+                assert!(
+                    TypeInfo::Struct(other).same(&TypeInfo::Struct(self)),
+                    "Different structures have the same name `{}`",
+                    self.name
+                );
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Description of structure member.
+#[derive(Clone, Copy, Debug)]
+pub struct MemberInfo {
+    /// Name of the member.
+    pub name: &'static str,
+
+    /// Type of the member.
+    pub ty: &'static TypeInfo,
+
+    /// Vectors are aligned as their components and vectors of 3 components are not padded.
+    /// Affects only layouts that pad vectors of 3 components, i.e. MSL `packed_float3`.
+    pub packed: bool,
+}
+
+impl MemberInfo {
+    /// Describe member with given name and type.
+    pub const fn new(name: &'static str, ty: &'static TypeInfo) -> Self {
+        MemberInfo {
+            name,
+            ty,
+            packed: false,
+        }
+    }
+
+    /// Mark member as packed.
+    pub const fn packed(self) -> Self {
+        MemberInfo {
+            packed: true,
+            ..self
+        }
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut index = 0;
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+        index += 1;
+    }
+    true
+}
+
+/// Write body of structure declaration in C-like shading language.
+/// `type_name` returns name of the member type that is not an array.
+pub(crate) fn declare_members(
+    source: &mut String,
+    info: &StructInfo,
+    type_name: impl Fn(&TypeInfo, &MemberInfo) -> String,
+) {
+    use std::fmt::Write;
+
+    source.push_str("\n{\n");
+    for member in info.members {
+        let mut ty = member.ty;
+        let mut dimensions = String::new();
+        while let TypeInfo::Array(elem, len) = *ty {
+            write!(dimensions, "[{}]", len).unwrap();
+            ty = elem;
+        }
+        writeln!(
+            source,
+            "    {} {}{};",
+            type_name(ty, member),
+            member.name,
+            dimensions
+        )
+        .unwrap();
+    }
+    source.push_str("};\n");
+}
@@ -0,0 +1,190 @@
+use crate::layout::{round_up, Layout, LayoutRules};
+use crate::uniform::Uniform;
+use std::{collections::VecDeque, fmt};
+
+/// Error returned when there is no space in `UniformRing` for a value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RingOverflow {
+    /// Size of the value.
+    pub size: usize,
+
+    /// Bytes that are not used by frames in flight.
+    pub available: usize,
+}
+
+impl fmt::Display for RingOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Value of {} bytes doesn't fit into {} bytes available in ring",
+            self.size, self.available
+        )
+    }
+}
+
+impl std::error::Error for RingOverflow {}
+
+/// Ring buffer for transient uniform data written every frame.
+///
+/// Values are written into `bytes` at offsets aligned to `alignment`,
+/// i.e. `minUniformBufferOffsetAlignment`, and their offsets are returned
+/// to be used as dynamic offsets.
+/// Space written during a frame is reused once the frame is complete.
+/// Frame is considered complete when frame `frames` ahead of it begins
+/// or when `complete` is called for it.
+#[derive(Debug)]
+pub struct UniformRing<B = Vec<u8>> {
+    bytes: B,
+    size: usize,
+    alignment: usize,
+    frames: u64,
+    /// Total number of bytes allocated.
+    head: usize,
+    /// Total number of bytes released.
+    tail: usize,
+    /// Frames in flight with their starting `head`.
+    in_flight: VecDeque<(u64, usize)>,
+}
+
+impl<B> UniformRing<B>
+where
+    B: AsMut<[u8]>,
+{
+    /// Create ring over `bytes` with given offset alignment and number of frames in flight.
+    ///
+    /// # Panics
+    ///
+    /// If `bytes` is empty or `alignment` or `frames` is zero.
+    pub fn new(mut bytes: B, alignment: usize, frames: usize) -> Self {
+        let size = bytes.as_mut().len();
+        assert!(size > 0, "Ring must not be empty");
+        assert!(alignment > 0, "Alignment must not be zero");
+        assert!(frames > 0, "There must be at least one frame in flight");
+        UniformRing {
+            bytes,
+            size,
+            alignment,
+            frames: frames as u64,
+            head: 0,
+            tail: 0,
+            in_flight: VecDeque::new(),
+        }
+    }
+
+    /// Begin frame with given index.
+    /// Frames `frames` or more behind it must be complete.
+    pub fn begin_frame(&mut self, frame: u64) {
+        if let Some(done) = frame.checked_sub(self.frames) {
+            self.complete(done);
+        }
+        self.in_flight.push_back((frame, self.head));
+    }
+
+    /// Mark all frames up to and including `frame` complete, e.g. after waiting for their fence.
+    pub fn complete(&mut self, frame: u64) {
+        while let Some(&(done, _)) = self.in_flight.front() {
+            if done > frame {
+                break;
+            }
+            self.in_flight.pop_front();
+            self.tail = self
+                .in_flight
+                .front()
+                .map_or(self.head, |&(_, start)| start);
+        }
+    }
+
+    /// Number of bytes used by frames in flight, including alignment padding.
+    pub fn used(&self) -> usize {
+        self.head - self.tail
+    }
+
+    /// Write `value` with `std140` layout and get its offset.
+    pub fn write<T: Uniform>(&mut self, value: &T) -> Result<usize, RingOverflow> {
+        let size = T::STD140_SIZE;
+        let offset = self.allocate(size, T::STD140_ALIGN)?;
+        value.write_std140(&mut self.bytes.as_mut()[offset..offset + size]);
+        Ok(offset)
+    }
+
+    /// Write `value` laid out with rules `L` and get its offset.
+    pub fn write_layout<L, T>(&mut self, value: &T) -> Result<usize, RingOverflow>
+    where
+        L: LayoutRules,
+        T: Uniform,
+    {
+        let size = <T as Layout<L>>::SIZE;
+        let offset = self.allocate(size, <T as Layout<L>>::ALIGN)?;
+        Layout::<L>::write_layout(value, &mut self.bytes.as_mut()[offset..offset + size]);
+        Ok(offset)
+    }
+
+    /// Bytes the ring writes into.
+    pub fn bytes(&mut self) -> &mut B {
+        &mut self.bytes
+    }
+
+    /// Get bytes back.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+
+    fn allocate(&mut self, size: usize, align: usize) -> Result<usize, RingOverflow> {
+        let position = self.head % self.size;
+        let mut start = self.head - position;
+        let mut offset = round_up(position, self.alignment.max(align));
+        if offset + size > self.size {
+            // Wrap around to the beginning.
+            start += self.size;
+            offset = 0;
+        }
+
+        let end = start + offset + size;
+        if end - self.tail > self.size {
+            return Err(RingOverflow {
+                size,
+                available: self.size - self.used(),
+            });
+        }
+        self.head = end;
+        Ok(offset)
+    }
+}
+
+#[test]
+fn test_uniform_ring() {
+    use crate::layout::Std430;
+    use crate::{mat4, vec2};
+
+    let mut ring = UniformRing::new(vec![0; 1024], 256, 2);
+    ring.begin_frame(0);
+    assert_eq!(ring.write(&vec2::from([1.0, 2.0])), Ok(0));
+    assert_eq!(ring.write(&mat4::default()), Ok(256));
+    assert_eq!(ring.write_layout::<Std430, _>(&[1.0f32; 3]), Ok(512));
+    assert_eq!(ring.used(), 524);
+    assert_eq!(&ring.bytes()[4..8], &2.0f32.to_ne_bytes());
+    assert_eq!(&ring.bytes()[516..520], &1.0f32.to_ne_bytes());
+
+    ring.begin_frame(1);
+    assert_eq!(ring.write(&mat4::default()), Ok(768));
+    assert_eq!(
+        ring.write(&vec2::default()),
+        Err(RingOverflow {
+            size: 8,
+            available: 192,
+        })
+    );
+
+    // Frame 0 is complete, its space is reused.
+    // Frame 1 starts right after the last value of frame 0.
+    ring.begin_frame(2);
+    assert_eq!(ring.used(), 308);
+    assert_eq!(ring.write(&vec2::default()), Ok(0));
+    assert_eq!(ring.write(&vec2::default()), Ok(256));
+    assert_eq!(ring.write(&vec2::default()), Ok(512));
+    assert!(ring.write(&vec2::default()).is_err());
+
+    ring.complete(2);
+    assert_eq!(ring.used(), 0);
+    assert_eq!(ring.write(&vec2::default()), Ok(768));
+}
@@ -1,13 +1,16 @@
 use crate::align::{Align4, Align8};
-use crate::uniform::{Std140, Uniform};
+use crate::reflect::{ScalarType, TypeInfo};
+use crate::uniform::{IsStd140, Std140, Uniform};
 
 macro_rules! impl_scalar {
-    ($type:ty : $align:tt) => {
+    ($type:ty : $align:tt, $scalar:ident) => {
         unsafe impl Std140 for $type {}
 
         impl Uniform for $type {
             type Align = $align;
             type Std140 = $type;
+            const TYPE_INFO: TypeInfo = TypeInfo::Scalar(ScalarType::$scalar);
+            const IS_STD140: IsStd140<Self> = IsStd140::new(true);
 
             fn std140(&self) -> $type {
                 *self
@@ -19,7 +22,7 @@ macro_rules! impl_scalar {
 /// Boolean value.
 #[derive(Clone, Copy, Debug, Default, PartialOrd, PartialEq, Ord, Eq, Hash)]
 pub struct boolean(u32);
-impl_scalar!(boolean: Align4);
+impl_scalar!(boolean: Align4, Bool);
 
 impl boolean {
     /// Create `boolean` from `bool`.
@@ -42,16 +45,16 @@ impl From<boolean> for bool {
 
 /// Signed integer value.
 pub type int = i32;
-impl_scalar!(int: Align4);
+impl_scalar!(int: Align4, Int);
 
 /// Unsigned integer value.
 pub type uint = u32;
-impl_scalar!(uint: Align4);
+impl_scalar!(uint: Align4, Uint);
 
 /// floating-point value.
 pub type float = f32;
-impl_scalar!(float: Align4);
+impl_scalar!(float: Align4, Float);
 
 /// Double-precision floating-point value.
 pub type double = f64;
-impl_scalar!(double: Align8);
+impl_scalar!(double: Align8, Double);
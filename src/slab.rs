@@ -0,0 +1,282 @@
+use crate::dirty::DirtyRanges;
+use crate::layout::{Layout, LayoutRules, Std140};
+use crate::uniform::Uniform;
+use std::{fmt, hash, marker::PhantomData, ops::Range};
+
+/// Stable index of value in `UniformSlab`.
+/// Can be passed to shaders as `uint` to index the array.
+/// Handles of removed values are invalidated, even if their slot is reused.
+pub struct Handle<T> {
+    index: u32,
+    generation: u32,
+    marker: PhantomData<fn(T)>,
+}
+
+impl<T> Handle<T> {
+    /// Index of the slot.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Handle<T> {}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> hash::Hash for Handle<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state)
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("index", &self.index)
+            .field("generation", &self.generation)
+            .finish()
+    }
+}
+
+/// Array of `Uniform` values laid out with rules `L` in slots with stable indices.
+/// Freed slots are reused and the array grows by `chunk` slots at a time.
+/// Keeps track of modified slots, so that only they could be uploaded.
+/// Once `capacity` changes the buffer must be recreated and all bytes uploaded.
+pub struct UniformSlab<T, L = Std140> {
+    bytes: Vec<u8>,
+    occupied: Vec<bool>,
+    /// Number of times each slot was freed.
+    generations: Vec<u32>,
+    free: Vec<u32>,
+    chunk: usize,
+    dirty: DirtyRanges,
+    marker: PhantomData<fn(T, L)>,
+}
+
+impl<T, L> UniformSlab<T, L>
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    /// Distance between starts of slots.
+    pub const STRIDE: usize = <T as Layout<L>>::ARRAY_STRIDE;
+
+    /// Create empty slab that grows by `chunk` slots.
+    ///
+    /// # Panics
+    ///
+    /// If `chunk` is zero.
+    pub fn new(chunk: usize) -> Self {
+        assert!(chunk > 0, "Chunk must not be empty");
+        UniformSlab {
+            bytes: Vec::new(),
+            occupied: Vec::new(),
+            generations: Vec::new(),
+            free: Vec::new(),
+            chunk,
+            dirty: DirtyRanges::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of occupied slots.
+    pub fn len(&self) -> usize {
+        self.capacity() - self.free.len()
+    }
+
+    /// Check if there are no occupied slots.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of slots.
+    pub fn capacity(&self) -> usize {
+        self.occupied.len()
+    }
+
+    /// Put value into a free slot.
+    pub fn insert(&mut self, value: T) -> Handle<T> {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.grow();
+                self.free.pop().unwrap()
+            }
+        };
+        self.occupied[index as usize] = true;
+        let handle = Handle {
+            index,
+            generation: self.generations[index as usize],
+            marker: PhantomData,
+        };
+        self.write(handle, &value);
+        handle
+    }
+
+    /// Free the slot.
+    /// Returns `false` if the value was already removed.
+    pub fn remove(&mut self, handle: Handle<T>) -> bool {
+        if !self.contains(handle) {
+            return false;
+        }
+        let index = handle.index as usize;
+        self.occupied[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(handle.index);
+        true
+    }
+
+    /// Check if the value wasn't removed.
+    pub fn contains(&self, handle: Handle<T>) -> bool {
+        let index = handle.index as usize;
+        self.occupied.get(index).copied().unwrap_or(false)
+            && self.generations[index] == handle.generation
+    }
+
+    /// Get value in the slot, unless it was removed.
+    pub fn get(&self, handle: Handle<T>) -> Option<T::Std140> {
+        if self.contains(handle) {
+            Some(<T as Layout<L>>::read_layout(
+                &self.bytes[self.byte_range(handle)],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Replace value in the slot.
+    ///
+    /// # Panics
+    ///
+    /// If the value was removed.
+    pub fn set(&mut self, handle: Handle<T>, value: T) {
+        assert!(
+            self.contains(handle),
+            "Value in slot {} was removed",
+            handle.index
+        );
+        self.write(handle, &value);
+    }
+
+    /// Bytes of all slots.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Range of bytes occupied by the slot.
+    pub fn byte_range(&self, handle: Handle<T>) -> Range<usize> {
+        let index = handle.index as usize;
+        index * Self::STRIDE..(index + 1) * Self::STRIDE
+    }
+
+    /// Ranges of bytes of slots modified since last call to `clear_dirty`.
+    /// Ranges are sorted and never adjacent.
+    pub fn dirty_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.dirty
+            .iter()
+            .map(|range| range.start * Self::STRIDE..range.end * Self::STRIDE)
+    }
+
+    /// Mark all slots as uploaded.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+
+    fn grow(&mut self) {
+        let capacity = self.capacity();
+        let new_capacity = capacity + self.chunk;
+        assert!(
+            u32::try_from(new_capacity - 1).is_ok(),
+            "Slab can't have more than 2^32 slots"
+        );
+        self.bytes.resize(new_capacity * Self::STRIDE, 0);
+        self.occupied.resize(new_capacity, false);
+        self.generations.resize(new_capacity, 0);
+        self.free
+            .extend((capacity..new_capacity).rev().map(|index| index as u32));
+    }
+
+    fn write(&mut self, handle: Handle<T>, value: &T) {
+        let range = self.byte_range(handle);
+        Layout::<L>::write_layout(value, &mut self.bytes[range]);
+        let index = handle.index as usize;
+        self.dirty.insert(index..index + 1);
+    }
+}
+
+impl<T, L> Clone for UniformSlab<T, L> {
+    fn clone(&self) -> Self {
+        UniformSlab {
+            bytes: self.bytes.clone(),
+            occupied: self.occupied.clone(),
+            generations: self.generations.clone(),
+            free: self.free.clone(),
+            chunk: self.chunk,
+            dirty: self.dirty.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, L> fmt::Debug for UniformSlab<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UniformSlab")
+            .field("bytes", &self.bytes.len())
+            .field("free", &self.free)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
+#[test]
+fn test_uniform_slab() {
+    use crate::layout::Std430;
+    use crate::vec3;
+
+    let mut slab = UniformSlab::<vec3>::new(2);
+    let a = slab.insert([1.0, 0.0, 0.0].into());
+    let b = slab.insert([2.0, 0.0, 0.0].into());
+    assert_eq!((a.index(), b.index()), (0, 1));
+    assert_eq!(slab.capacity(), 2);
+
+    let c = slab.insert([3.0, 0.0, 0.0].into());
+    assert_eq!(c.index(), 2);
+    assert_eq!(slab.capacity(), 4);
+    assert_eq!(slab.as_bytes().len(), 64);
+    assert!(slab.dirty_ranges().eq(Some(0..48)));
+
+    slab.clear_dirty();
+    assert!(slab.remove(b));
+    assert!(!slab.remove(b));
+    assert!(slab.get(b).is_none());
+    assert_eq!(slab.len(), 2);
+
+    let d = slab.insert([4.0, 0.0, 0.0].into());
+    assert_eq!(d.index(), 1);
+    // Handle of removed value doesn't refer to the new value in its slot.
+    assert_ne!(b, d);
+    assert!(!slab.contains(b));
+    assert!(slab.get(b).is_none());
+    assert!(!slab.remove(b));
+    assert_eq!(<[f32; 3]>::from(slab.get(d).unwrap()), [4.0, 0.0, 0.0]);
+    slab.set(a, [5.0, 0.0, 0.0].into());
+    assert!(slab.dirty_ranges().eq(Some(0..32)));
+
+    let mut slab = UniformSlab::<f32, Std430>::new(8);
+    let handles = (0..3).map(|x| slab.insert(x as f32)).collect::<Vec<_>>();
+    assert_eq!(UniformSlab::<f32, Std430>::STRIDE, 4);
+    assert_eq!(slab.byte_range(handles[2]), 8..12);
+}
@@ -1,11 +1,18 @@
+use crate::array::Element;
+use crate::reflect::TypeInfo;
+use std::marker::PhantomData;
+use std::mem::{align_of, size_of, size_of_val};
+
 /// Special marker trait implemented only for `std140` types.
 ///
 /// # Safety
 /// The type must not have any padding bytes
+/// and any bit pattern must be a valid value of the type,
+/// as values are read from bytes by `Layout::read_layout`.
 pub unsafe trait Std140: Sized + Uniform<Std140 = Self> {
     /// Convert to bytes-slice.
     fn as_raw(&self) -> &[u8] {
-        use std::{mem::size_of, slice::from_raw_parts};
+        use std::slice::from_raw_parts;
         unsafe { from_raw_parts(self as *const Self as *const u8, size_of::<Self>()) }
     }
 }
@@ -19,6 +26,246 @@ pub trait Uniform: Copy {
     /// Type that contain same data with memory layout matching glsl's `layout(std140)`.
     type Std140: Std140;
 
+    /// Description of glsl type this type represents.
+    /// Defaults to description of `Std140` type,
+    /// thus types that are their own `Std140` representation must define it.
+    const TYPE_INFO: TypeInfo = <Self::Std140 as Uniform>::TYPE_INFO;
+
+    /// Size of the type in `std140` layout.
+    const STD140_SIZE: usize = size_of::<Self::Std140>();
+
+    /// Alignment of the type in `std140` layout.
+    const STD140_ALIGN: usize = if align_of::<Self::Align>() > align_of::<Self::Std140>() {
+        align_of::<Self::Align>()
+    } else {
+        align_of::<Self::Std140>()
+    };
+
+    /// Type is `Self::Std140` and has no padding bytes.
+    /// Enables copying slices of the type as is.
+    #[doc(hidden)]
+    const IS_STD140: IsStd140<Self> = IsStd140::NO;
+
     /// Get aligned data from structure.
     fn std140(&self) -> Self::Std140;
+
+    /// Write data in `std140` layout into `out` without building `Std140` value.
+    /// Padding bytes are zeroed.
+    ///
+    /// # Panics
+    ///
+    /// If `out` is shorter than `STD140_SIZE`.
+    fn write_std140(&self, out: &mut [u8]) {
+        out[..Self::STD140_SIZE].copy_from_slice(self.std140().as_raw());
+    }
+
+    /// Append `values` to `out` as `std140` array.
+    /// Slices of types that are already laid out as `std140` array are copied at once.
+    fn slice_to_std140(values: &[Self], out: &mut Vec<u8>) {
+        let stride = size_of::<Element<Self::Std140>>();
+        if Self::IS_STD140.0 && size_of::<Self>() == stride {
+            // Type has no padding bytes.
+            let bytes = unsafe {
+                std::slice::from_raw_parts(values.as_ptr() as *const u8, size_of_val(values))
+            };
+            out.extend_from_slice(bytes);
+        } else {
+            let start = out.len();
+            out.resize(start + stride * values.len(), 0);
+            for (value, out) in values.iter().zip(out[start..].chunks_mut(stride)) {
+                value.write_std140(out);
+            }
+        }
+    }
+}
+
+/// Flag of `Uniform::IS_STD140`.
+/// Only this crate can set it, so safe implementations of `Uniform` can't enable copying
+/// slices of types with padding bytes.
+///
+/// ```rust,compile_fail
+/// # use glsl_layout::{vec3, IsStd140, Uniform};
+/// #[derive(Clone, Copy)]
+/// struct Padded(vec3);
+///
+/// impl Uniform for Padded {
+///     type Align = <vec3 as Uniform>::Align;
+///     type Std140 = vec3;
+///     const IS_STD140: IsStd140<Self> = IsStd140::new(true);
+///
+///     fn std140(&self) -> vec3 {
+///         self.0
+///     }
+/// }
+/// ```
+#[doc(hidden)]
+pub struct IsStd140<T>(pub(crate) bool, PhantomData<fn(T)>);
+
+impl<T> IsStd140<T> {
+    /// Type may have padding bytes.
+    pub const NO: Self = IsStd140(false, PhantomData);
+
+    pub(crate) const fn new(value: bool) -> Self {
+        IsStd140(value, PhantomData)
+    }
+}
+
+/// Assert at compile time that the block fits into given number of bytes,
+/// i.e. `GL_MAX_UNIFORM_BLOCK_SIZE` or push constants limit.
+/// Size in `std140` layout is checked unless layout rules are specified.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[macro_use] extern crate glsl_layout;
+/// # use glsl_layout::{mat4, vec4, Uniform};
+/// #[derive(Clone, Copy, Uniform)]
+/// struct PushConstants {
+///     transform: mat4,
+///     color: vec4,
+/// }
+///
+/// assert_block_fits!(PushConstants, 128);
+/// assert_block_fits!(PushConstants, 80, glsl_layout::layout::Std430);
+/// # fn main() {}
+/// ```
+///
+/// ```rust,compile_fail
+/// # #[macro_use] extern crate glsl_layout;
+/// # use glsl_layout::{mat4, Uniform};
+/// #[derive(Clone, Copy, Uniform)]
+/// struct PushConstants {
+///     transforms: [mat4; 3],
+/// }
+///
+/// assert_block_fits!(PushConstants, 128);
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! assert_block_fits {
+    ($type:ty, $limit:expr) => {
+        const _: () = assert!(
+            <$type as $crate::Uniform>::STD140_SIZE <= $limit,
+            concat!(
+                "`",
+                stringify!($type),
+                "` exceeds ",
+                stringify!($limit),
+                " bytes"
+            ),
+        );
+    };
+    ($type:ty, $limit:expr, $layout:ty) => {
+        const _: () = assert!(
+            <$type as $crate::Layout<$layout>>::SIZE <= $limit,
+            concat!(
+                "`",
+                stringify!($type),
+                "` exceeds ",
+                stringify!($limit),
+                " bytes"
+            ),
+        );
+    };
+}
+
+#[test]
+fn test_std140_size() {
+    use crate::{dvec3, mat3, vec3};
+
+    assert_eq!(f32::STD140_SIZE, 4);
+    assert_eq!(f32::STD140_ALIGN, 4);
+    assert_eq!(vec3::STD140_SIZE, 12);
+    assert_eq!(vec3::STD140_ALIGN, 16);
+    assert_eq!(dvec3::STD140_ALIGN, 32);
+    assert_eq!(mat3::STD140_SIZE, 48);
+    assert_eq!(<[f32; 3]>::STD140_SIZE, 48);
+    assert_eq!(<[f32; 3]>::STD140_ALIGN, 16);
+
+    assert_block_fits!(mat3, 48);
+    assert_block_fits!([f32; 3], 12, crate::layout::Std430);
+}
+
+#[test]
+fn test_write_std140() {
+    use crate as glsl_layout;
+    use crate::layout::{Layout, Std140 as Std140Rules};
+    use crate::{dmat4x3, float, vec3};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Inner {
+        a: float,
+        b: vec3,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Block {
+        scale: float,
+        inners: [Inner; 3],
+        position: vec3,
+        transforms: [dmat4x3; 2],
+    }
+
+    let block = Block {
+        scale: 1.0,
+        inners: [Inner {
+            a: 2.0,
+            b: [3.0, 4.0, 5.0].into(),
+        }; 3],
+        position: [6.0, 7.0, 8.0].into(),
+        transforms: [[[9.0, 10.0, 11.0]; 4].into(); 2],
+    };
+
+    let mut bytes = vec![0xAA; Block::STD140_SIZE];
+    block.write_std140(&mut bytes);
+    assert_eq!(bytes, Layout::<Std140Rules>::to_bytes(&block));
+}
+
+#[test]
+fn test_slice_to_std140() {
+    use crate::{mat3, mat4, vec3, vec4};
+
+    const { assert!(vec4::IS_STD140.0 && mat4::IS_STD140.0 && !mat3::IS_STD140.0) };
+
+    fn check<T: Uniform>(values: &[T]) {
+        let mut bytes = vec![1, 2, 3];
+        T::slice_to_std140(values, &mut bytes);
+        let mut expected = vec![1, 2, 3];
+        expected.extend(values.iter().flat_map(|value| {
+            let mut element = vec![0; size_of::<Element<T::Std140>>()];
+            value.write_std140(&mut element);
+            element
+        }));
+        assert_eq!(bytes, expected);
+    }
+
+    check(&[vec4::from([1.0, 2.0, 3.0, 4.0]); 3]);
+    check(&[mat4::from([[1.0, 2.0, 3.0, 4.0]; 4]); 2]);
+    check(&[vec3::from([1.0, 2.0, 3.0]); 3]);
+    check(&[mat3::from([[1.0, 2.0, 3.0]; 3]); 2]);
+    check(&[1.0f32, 2.0, 3.0]);
+}
+
+#[test]
+fn test_default_type_info() {
+    use crate::{vec2, Std140 as _};
+
+    #[derive(Clone, Copy)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    impl Uniform for Point {
+        type Align = <vec2 as Uniform>::Align;
+        type Std140 = vec2;
+
+        fn std140(&self) -> vec2 {
+            [self.x, self.y].into()
+        }
+    }
+
+    let point = Point { x: 1.0, y: 2.0 };
+    assert_eq!(point.std140().as_raw(), vec2::from([1.0, 2.0]).as_raw());
+    assert!(matches!(Point::TYPE_INFO, TypeInfo::Vector(_, 2)));
 }
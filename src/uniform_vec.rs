@@ -0,0 +1,244 @@
+use crate::dirty::DirtyRanges;
+use crate::layout::{Layout, LayoutRules, Std140, Std430};
+use crate::uniform::Uniform;
+use std::{fmt, iter::FromIterator, marker::PhantomData, ops::Range};
+
+/// Growable array of `Uniform` values laid out with rules `L`, ready to be uploaded into buffer.
+/// Keeps track of modified elements, so that only they could be uploaded.
+///
+/// ```rust,compile_fail
+/// # use glsl_layout::{Std140Vec, Uniform};
+/// #[derive(Clone, Copy, Uniform)]
+/// struct Empty {}
+///
+/// let _ = Std140Vec::<Empty>::new();
+/// ```
+pub struct UniformVec<T, L = Std140> {
+    bytes: Vec<u8>,
+    dirty: DirtyRanges,
+    marker: PhantomData<fn(T, L)>,
+}
+
+/// Array of values with `std140` layout.
+pub type Std140Vec<T> = UniformVec<T, Std140>;
+
+/// Array of values with `std430` layout.
+pub type Std430Vec<T> = UniformVec<T, Std430>;
+
+impl<T, L> UniformVec<T, L>
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    /// Distance between starts of elements.
+    /// Zero-sized types are rejected at compile time.
+    pub const STRIDE: usize = {
+        let stride = <T as Layout<L>>::ARRAY_STRIDE;
+        assert!(stride > 0, "`UniformVec` can't hold zero-sized values");
+        stride
+    };
+
+    /// Create empty array.
+    pub fn new() -> Self {
+        let _ = Self::STRIDE;
+        UniformVec {
+            bytes: Vec::new(),
+            dirty: DirtyRanges::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Create empty array with space for `capacity` elements.
+    pub fn with_capacity(capacity: usize) -> Self {
+        UniformVec {
+            bytes: Vec::with_capacity(capacity * Self::STRIDE),
+            dirty: DirtyRanges::default(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / Self::STRIDE
+    }
+
+    /// Check if there are no elements.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Append value to the end.
+    pub fn push(&mut self, value: T) {
+        let index = self.len();
+        self.bytes.resize(self.bytes.len() + Self::STRIDE, 0);
+        self.write(index, &value);
+    }
+
+    /// Get element.
+    pub fn get(&self, index: usize) -> Option<T::Std140> {
+        if index < self.len() {
+            Some(<T as Layout<L>>::read_layout(
+                &self.bytes[self.byte_range(index)],
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Replace element.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len(), "Index {} is out of bounds", index);
+        self.write(index, &value);
+    }
+
+    /// Remove all elements after first `len`.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            self.bytes.truncate(len * Self::STRIDE);
+            self.dirty.truncate(len);
+        }
+    }
+
+    /// Remove all elements.
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    /// Bytes of all elements.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Range of bytes occupied by element.
+    /// Includes padding up to the next element.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is out of bounds.
+    pub fn byte_range(&self, index: usize) -> Range<usize> {
+        assert!(index < self.len(), "Index {} is out of bounds", index);
+        index * Self::STRIDE..(index + 1) * Self::STRIDE
+    }
+
+    /// Ranges of bytes of elements modified since last call to `clear_dirty`.
+    /// Ranges are sorted and never adjacent.
+    pub fn dirty_ranges(&self) -> impl Iterator<Item = Range<usize>> + '_ {
+        self.dirty
+            .iter()
+            .map(|range| range.start * Self::STRIDE..range.end * Self::STRIDE)
+    }
+
+    /// Mark all elements as uploaded.
+    pub fn clear_dirty(&mut self) {
+        self.dirty.clear();
+    }
+
+    fn write(&mut self, index: usize, value: &T) {
+        let range = self.byte_range(index);
+        Layout::<L>::write_layout(value, &mut self.bytes[range]);
+        self.dirty.insert(index..index + 1);
+    }
+}
+
+impl<T, L> Default for UniformVec<T, L>
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    fn default() -> Self {
+        UniformVec::new()
+    }
+}
+
+impl<T, L> Clone for UniformVec<T, L> {
+    fn clone(&self) -> Self {
+        UniformVec {
+            bytes: self.bytes.clone(),
+            dirty: self.dirty.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T, L> fmt::Debug for UniformVec<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UniformVec")
+            .field("bytes", &self.bytes.len())
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
+impl<T, L> Extend<T> for UniformVec<T, L>
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.bytes.reserve(iter.size_hint().0 * Self::STRIDE);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, L> FromIterator<T> for UniformVec<T, L>
+where
+    T: Uniform,
+    L: LayoutRules,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = UniformVec::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+#[test]
+fn test_uniform_vec() {
+    use crate as glsl_layout;
+    use crate::{float, vec3};
+
+    #[derive(Clone, Copy, Debug, Default, Uniform)]
+    struct Instance {
+        position: vec3,
+        scale: float,
+        weights: [float; 2],
+    }
+
+    let instance = |x: f32| Instance {
+        position: [x, 0.0, 0.0].into(),
+        scale: x,
+        weights: [x, x],
+    };
+
+    let mut std140 = (0..4).map(|x| instance(x as f32)).collect::<Std140Vec<_>>();
+    assert_eq!(Std140Vec::<Instance>::STRIDE, 48);
+    assert_eq!(std140.len(), 4);
+    assert_eq!(std140.as_bytes().len(), 192);
+    assert!(std140.dirty_ranges().eq(Some(0..192)));
+
+    std140.clear_dirty();
+    std140.set(1, instance(5.0));
+    std140.set(3, instance(6.0));
+    std140.push(instance(7.0));
+    assert_eq!(
+        std140.dirty_ranges().collect::<Vec<_>>(),
+        [48..96, 144..240]
+    );
+    assert_eq!(std140.byte_range(2), 96..144);
+
+    let element = std140.get(1).unwrap();
+    assert_eq!(element.scale, 5.0);
+    assert_eq!(element.weights.iter().nth(1), Some(&5.0));
+    assert!(std140.get(5).is_none());
+
+    let std430 = (0..4).map(|x| instance(x as f32)).collect::<Std430Vec<_>>();
+    assert_eq!(Std430Vec::<Instance>::STRIDE, 32);
+    assert_eq!(std430.get(3).unwrap().weights.iter().next(), Some(&3.0));
+}
@@ -0,0 +1,268 @@
+use crate::glsl;
+use crate::layout::{members, round_up, type_layout, write_relayout, Layout, LayoutRules, Std430};
+use crate::reflect::TypeInfo;
+use crate::uniform::{Std140 as _, Uniform};
+use std::{fmt, marker::PhantomData};
+
+/// Error returned when bytes of `UnsizedBlock` end in the middle of the header
+/// or of an array element.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockSizeError {
+    /// Number of bytes.
+    pub len: usize,
+
+    /// Offset of the array.
+    pub items_offset: usize,
+
+    /// Distance between starts of array elements.
+    pub stride: usize,
+}
+
+impl fmt::Display for BlockSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes are not {} bytes of header followed by elements of {} bytes",
+            self.len, self.items_offset, self.stride
+        )
+    }
+}
+
+impl std::error::Error for BlockSizeError {}
+
+/// Storage buffer block with members of structure `H`
+/// followed by runtime-sized array of `T` laid out with rules `L`.
+///
+/// ```glsl
+/// layout(std430) buffer Particles
+/// {
+///     uint count;
+///     Particle items[];
+/// };
+/// ```
+pub struct UnsizedBlock<H, T, L = Std430> {
+    /// Members before the array.
+    pub header: H,
+
+    /// Elements of the array.
+    pub items: Vec<T>,
+
+    marker: PhantomData<fn(L)>,
+}
+
+impl<H, T, L> UnsizedBlock<H, T, L>
+where
+    H: Uniform,
+    T: Uniform,
+    L: LayoutRules,
+{
+    /// Distance between starts of array elements.
+    /// Zero-sized elements are rejected at compile time.
+    pub const STRIDE: usize = {
+        let stride = <T as Layout<L>>::ARRAY_STRIDE;
+        assert!(stride > 0, "`UnsizedBlock` can't hold zero-sized elements");
+        stride
+    };
+
+    /// Create block from header and array elements.
+    pub fn new(header: H, items: Vec<T>) -> Self {
+        UnsizedBlock {
+            header,
+            items,
+            marker: PhantomData,
+        }
+    }
+
+    /// Offset of the array.
+    /// The array starts right after the last member of the header, not after its padding.
+    ///
+    /// # Panics
+    ///
+    /// If `H` is not a structure.
+    pub fn items_offset() -> usize {
+        let info = match H::TYPE_INFO {
+            TypeInfo::Struct(info) => info,
+            _ => panic!("Header must be a structure"),
+        };
+        let end = members::<L>(info)
+            .last()
+            .map_or(0, |(_, offset, layout)| offset + layout.size);
+        let align = type_layout::<L>(&T::TYPE_INFO).align.max(L::ARRAY_ALIGN);
+        round_up(end, align)
+    }
+
+    /// Size of the block.
+    pub fn size(&self) -> usize {
+        Self::items_offset() + self.items.len() * Self::STRIDE
+    }
+
+    /// Write the block into `out`. Padding bytes are zeroed.
+    ///
+    /// # Panics
+    ///
+    /// If `out` is shorter than `size()`.
+    pub fn write(&self, out: &mut [u8]) {
+        let out = &mut out[..self.size()];
+        write_relayout::<L>(&H::TYPE_INFO, self.header.std140().as_raw(), out);
+
+        let offset = Self::items_offset();
+        for (index, item) in self.items.iter().enumerate() {
+            Layout::<L>::write_layout(item, &mut out[offset + index * Self::STRIDE..]);
+        }
+    }
+
+    /// Get bytes of the block.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![0; self.size()];
+        self.write(&mut bytes);
+        bytes
+    }
+
+    /// Read block from `bytes`.
+    /// Number of elements is determined by the length of `bytes`,
+    /// which must end right after the header or after the last element.
+    pub fn from_bytes(
+        bytes: &[u8],
+    ) -> Result<UnsizedBlock<H::Std140, T::Std140, L>, BlockSizeError> {
+        let offset = Self::items_offset();
+        if bytes.len() < offset || !(bytes.len() - offset).is_multiple_of(Self::STRIDE) {
+            return Err(BlockSizeError {
+                len: bytes.len(),
+                items_offset: offset,
+                stride: Self::STRIDE,
+            });
+        }
+        let items = &bytes[offset..];
+
+        // Header may be padded over the beginning of the array.
+        let mut header = vec![0; <H as Layout<L>>::SIZE];
+        let end = header.len().min(offset);
+        header[..end].copy_from_slice(&bytes[..end]);
+
+        Ok(UnsizedBlock::new(
+            <H as Layout<L>>::read_layout(&header),
+            items
+                .chunks_exact(Self::STRIDE)
+                .map(<T as Layout<L>>::read_layout)
+                .collect(),
+        ))
+    }
+
+    /// Generate GLSL declaration of `buffer` block with array named `items`
+    /// preceded by declarations of structures it uses.
+    ///
+    /// # Panics
+    ///
+    /// If `H` is not a structure or glsl has no layout `L`.
+    pub fn declaration(name: &str, items: &str, binding: Option<u32>) -> String {
+        glsl::block::<L>(
+            "buffer",
+            name,
+            binding,
+            &H::TYPE_INFO,
+            Some((items, &T::TYPE_INFO)),
+        )
+    }
+}
+
+impl<H, T, L> Clone for UnsizedBlock<H, T, L>
+where
+    H: Clone,
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        UnsizedBlock {
+            header: self.header.clone(),
+            items: self.items.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<H, T, L> fmt::Debug for UnsizedBlock<H, T, L>
+where
+    H: fmt::Debug,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnsizedBlock")
+            .field("header", &self.header)
+            .field("items", &self.items)
+            .finish()
+    }
+}
+
+#[test]
+fn test_unsized_block() {
+    use crate as glsl_layout;
+    use crate::{float, uint, vec3};
+
+    #[derive(Clone, Copy, Debug, Default, Uniform)]
+    struct Header {
+        count: uint,
+    }
+
+    #[derive(Clone, Copy, Debug, Default, Uniform)]
+    struct Particle {
+        position: vec3,
+        mass: float,
+    }
+
+    type Particles = UnsizedBlock<Header, Particle>;
+
+    let particle = |x: f32| Particle {
+        position: [x, 0.0, 0.0].into(),
+        mass: x,
+    };
+    let block = Particles::new(Header { count: 2 }, vec![particle(1.0), particle(2.0)]);
+    assert_eq!(Particles::items_offset(), 16);
+    assert_eq!(Particles::STRIDE, 16);
+    assert_eq!(block.size(), 48);
+
+    let bytes = block.to_bytes();
+    assert_eq!(&bytes[0..4], &2u32.to_ne_bytes());
+    assert_eq!(&bytes[44..48], &2.0f32.to_ne_bytes());
+
+    let read = Particles::from_bytes(&bytes).unwrap();
+    assert_eq!(read.header.count, 2);
+    assert_eq!(read.items.len(), 2);
+    assert_eq!(read.items[1].mass, 2.0);
+
+    assert_eq!(
+        Particles::declaration("Particles", "items", Some(0)),
+        "struct Particle
+{
+    vec3 position;
+    float mass;
+};
+
+layout(std430, binding = 0) buffer Particles
+{
+    uint count;
+    Particle items[];
+};
+"
+    );
+
+    // Array of scalars follows the header immediately in std430, but not in std140.
+    assert_eq!(UnsizedBlock::<Header, float>::items_offset(), 4);
+    assert_eq!(
+        UnsizedBlock::<Header, float, crate::layout::Std140>::items_offset(),
+        16
+    );
+    let block = UnsizedBlock::<Header, float>::new(Header { count: 3 }, vec![1.0, 2.0, 3.0]);
+    assert_eq!(block.size(), 16);
+    let read = UnsizedBlock::<Header, float>::from_bytes(&block.to_bytes()).unwrap();
+    assert_eq!(read.items, [1.0, 2.0, 3.0]);
+
+    // Partial element is an error rather than being dropped.
+    assert_eq!(
+        UnsizedBlock::<Header, float>::from_bytes(&block.to_bytes()[..14]).unwrap_err(),
+        BlockSizeError {
+            len: 14,
+            items_offset: 4,
+            stride: 4,
+        }
+    );
+    assert!(UnsizedBlock::<Header, float>::from_bytes(&[0; 2]).is_err());
+}
@@ -1,7 +1,8 @@
 use crate::align::{Align16, Align32, Align8};
 use crate::array::MapArray;
+use crate::reflect::TypeInfo;
 use crate::scalar::{boolean, double, float, int, uint};
-use crate::uniform::{Std140, Uniform};
+use crate::uniform::{IsStd140, Std140, Uniform};
 
 macro_rules! implement_vec {
     ($vec:ident => [$type:ty; $size:tt]: $align:tt) => {
@@ -14,6 +15,12 @@ macro_rules! implement_vec {
             }
         }
 
+        impl From<$vec> for [$type; $size] {
+            fn from(value: $vec) -> Self {
+                value.0
+            }
+        }
+
         impl AsRef<[$type; $size]> for $vec {
             fn as_ref(&self) -> &[$type; $size] {
                 &self.0
@@ -43,6 +50,11 @@ macro_rules! implement_vec {
         impl Uniform for $vec {
             type Align = $align;
             type Std140 = $vec;
+            const TYPE_INFO: TypeInfo = match <$type as Uniform>::TYPE_INFO {
+                TypeInfo::Scalar(scalar) => TypeInfo::Vector(scalar, $size),
+                _ => unreachable!(),
+            };
+            const IS_STD140: IsStd140<Self> = IsStd140::new(true);
 
             fn std140(&self) -> $vec {
                 *self
@@ -0,0 +1,340 @@
+//! WGSL layouts of `uniform` and `storage` address spaces.
+//!
+//! In `uniform` address space arrays and structures are aligned to 16 bytes
+//! and array strides are multiples of 16. In `storage` address space natural alignment is used.
+//! Declarations get `@align` and `@size` attributes where WGSL would otherwise
+//! lay out members differently, i.e. where `uniform` address space requires larger alignment.
+
+use crate::layout::{
+    array_stride, members, round_up, struct_layout, type_layout, Layout, LayoutRules, WgslStorage,
+    WgslUniform,
+};
+use crate::reflect::{MemberInfo, ScalarType, StructInfo, TypeInfo};
+use crate::uniform::Uniform;
+use std::fmt::{self, Write};
+
+/// Address space of WGSL variable.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AddressSpace {
+    /// `var<uniform>`.
+    Uniform,
+    /// `var<storage>`.
+    Storage,
+}
+
+/// Error of WGSL declaration generation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// Only structures can be declared.
+    NotStruct,
+
+    /// WGSL does not allow structures without members.
+    EmptyStruct(&'static str),
+
+    /// Member type can't be used in host-shareable WGSL structures.
+    UnsupportedType {
+        /// Name of the member.
+        member: &'static str,
+        /// Name of the type.
+        ty: &'static str,
+    },
+
+    /// WGSL array stride is determined by the element type
+    /// and differs from the stride required by address space.
+    ArrayStride {
+        /// Name of the member.
+        member: &'static str,
+        /// Stride required by address space.
+        stride: usize,
+        /// Stride of WGSL array.
+        natural: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Error::NotStruct => write!(f, "Only structures can be declared"),
+            Error::EmptyStruct(name) => write!(f, "Structure `{}` has no members", name),
+            Error::UnsupportedType { member, ty } => {
+                write!(
+                    f,
+                    "Member `{}` has type `{}` unsupported by WGSL",
+                    member, ty
+                )
+            }
+            Error::ArrayStride {
+                member,
+                stride,
+                natural,
+            } => write!(
+                f,
+                "Member `{}` requires array stride {} while WGSL array has stride {}",
+                member, stride, natural
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Size of `T` laid out in WGSL address space.
+pub const fn size_of<T: Uniform>(space: AddressSpace) -> usize {
+    match space {
+        AddressSpace::Uniform => <T as Layout<WgslUniform>>::SIZE,
+        AddressSpace::Storage => <T as Layout<WgslStorage>>::SIZE,
+    }
+}
+
+/// Write `value` laid out in WGSL address space into `out`.
+/// Padding bytes are zeroed.
+///
+/// # Panics
+///
+/// If `out` is shorter than `size_of::<T>(space)`.
+pub fn write<T: Uniform>(value: &T, space: AddressSpace, out: &mut [u8]) {
+    match space {
+        AddressSpace::Uniform => Layout::<WgslUniform>::write_layout(value, out),
+        AddressSpace::Storage => Layout::<WgslStorage>::write_layout(value, out),
+    }
+}
+
+/// Get `value` laid out in WGSL address space.
+pub fn to_bytes<T: Uniform>(value: &T, space: AddressSpace) -> Vec<u8> {
+    match space {
+        AddressSpace::Uniform => Layout::<WgslUniform>::to_bytes(value),
+        AddressSpace::Storage => Layout::<WgslStorage>::to_bytes(value),
+    }
+}
+
+/// Generate WGSL declarations of structure `T` and structures it uses
+/// for variables in given address space.
+///
+/// # Panics
+///
+/// If different structures used by `T` have the same name.
+pub fn declaration<T: Uniform>(space: AddressSpace) -> Result<String, Error> {
+    match space {
+        AddressSpace::Uniform => declare::<WgslUniform>(&T::TYPE_INFO),
+        AddressSpace::Storage => declare::<WgslStorage>(&T::TYPE_INFO),
+    }
+}
+
+/// Alignments WGSL gives to declared structures.
+type Aligns = Vec<(&'static str, usize)>;
+
+fn declare<L: LayoutRules>(ty: &TypeInfo) -> Result<String, Error> {
+    if !matches!(ty, TypeInfo::Struct(_)) {
+        return Err(Error::NotStruct);
+    }
+
+    let mut source = String::new();
+    let mut aligns = Aligns::new();
+    for (index, info) in ty.structs().into_iter().enumerate() {
+        if index > 0 {
+            source.push('\n');
+        }
+        declare_struct::<L>(&mut source, info, &mut aligns)?;
+    }
+    Ok(source)
+}
+
+fn declare_struct<L: LayoutRules>(
+    source: &mut String,
+    info: &StructInfo,
+    aligns: &mut Aligns,
+) -> Result<(), Error> {
+    if info.members.is_empty() {
+        return Err(Error::EmptyStruct(info.name));
+    }
+
+    let mut declared = Vec::new();
+    let mut align = 1;
+    let mut end = 0;
+    let mut last = 0;
+    for (member, offset, layout) in members::<L>(info) {
+        let ty = type_name::<L>(member, member.ty, aligns)?;
+        let natural = natural_align::<L>(member.ty, aligns);
+        let mut attributes = String::new();
+        if round_up(end, natural) == offset {
+            align = align.max(natural);
+        } else {
+            write!(attributes, "@align({}) ", layout.align).unwrap();
+            align = align.max(layout.align);
+        }
+        declared.push((attributes, member.name, ty));
+        end = offset + layout.size;
+        last = offset;
+    }
+
+    let size = struct_layout::<L>(info).size;
+    if round_up(end, align) != size {
+        let (attributes, _, _) = declared.last_mut().unwrap();
+        write!(attributes, "@size({}) ", size - last).unwrap();
+    }
+    aligns.push((info.name, align));
+
+    writeln!(source, "struct {} {{", info.name).unwrap();
+    for (attributes, name, ty) in declared {
+        writeln!(source, "    {}{}: {},", attributes, name, ty).unwrap();
+    }
+    source.push_str("}\n");
+    Ok(())
+}
+
+/// Alignment WGSL gives to the type.
+fn natural_align<L: LayoutRules>(ty: &TypeInfo, aligns: &Aligns) -> usize {
+    match *ty {
+        TypeInfo::Array(elem, _) => natural_align::<L>(elem, aligns),
+        TypeInfo::Struct(info) => aligns
+            .iter()
+            .find(|&&(name, _)| name == info.name)
+            .map(|&(_, align)| align)
+            .expect("Structures are declared after their dependencies"),
+        _ => type_layout::<L>(ty).align,
+    }
+}
+
+fn scalar_name(member: &MemberInfo, scalar: ScalarType) -> Result<&'static str, Error> {
+    match scalar {
+        ScalarType::Int => Ok("i32"),
+        ScalarType::Uint => Ok("u32"),
+        ScalarType::Float => Ok("f32"),
+        ScalarType::Bool => Err(Error::UnsupportedType {
+            member: member.name,
+            ty: "bool",
+        }),
+        ScalarType::Double => Err(Error::UnsupportedType {
+            member: member.name,
+            ty: "f64",
+        }),
+    }
+}
+
+fn type_name<L: LayoutRules>(
+    member: &MemberInfo,
+    ty: &TypeInfo,
+    aligns: &Aligns,
+) -> Result<String, Error> {
+    Ok(match *ty {
+        TypeInfo::Scalar(scalar) => scalar_name(member, scalar)?.to_owned(),
+        TypeInfo::Vector(scalar, len) => format!("vec{}<{}>", len, scalar_name(member, scalar)?),
+        TypeInfo::Matrix {
+            scalar,
+            columns,
+            rows,
+        } => format!("mat{}x{}<{}>", columns, rows, scalar_name(member, scalar)?),
+        TypeInfo::Array(elem, len) => {
+            let stride = array_stride::<L>(elem, false);
+            let natural = round_up(
+                type_layout::<L>(elem).size,
+                natural_align::<L>(elem, aligns),
+            );
+            if stride != natural {
+                return Err(Error::ArrayStride {
+                    member: member.name,
+                    stride,
+                    natural,
+                });
+            }
+            format!("array<{}, {}>", type_name::<L>(member, elem, aligns)?, len)
+        }
+        TypeInfo::Struct(info) => info.name.to_owned(),
+    })
+}
+
+#[test]
+fn test_wgsl() {
+    use crate as glsl_layout;
+    use crate::{boolean, float, mat3, uint, vec2, vec3, vec4};
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Inner {
+        s: float,
+        t: float,
+    }
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Params {
+        position: vec3,
+        scale: float,
+        rotation: mat3,
+        count: uint,
+        inner: Inner,
+        colors: [vec4; 2],
+    }
+
+    // position: 0, scale: 12, rotation: 16, count: 64, inner: 80, colors: 96.
+    assert_eq!(size_of::<Params>(AddressSpace::Uniform), 128);
+    // count: 64, inner: 68, colors: 80.
+    assert_eq!(size_of::<Params>(AddressSpace::Storage), 112);
+    assert_eq!(size_of::<Inner>(AddressSpace::Storage), 8);
+
+    let params = Params {
+        scale: 1.0,
+        rotation: [[2.0, 0.0, 0.0], [3.0, 0.0, 0.0], [4.0, 0.0, 0.0]].into(),
+        count: 5,
+        ..Default::default()
+    };
+    let bytes = to_bytes(&params, AddressSpace::Uniform);
+    assert_eq!(&bytes[12..16], &1.0f32.to_ne_bytes());
+    assert_eq!(&bytes[32..36], &3.0f32.to_ne_bytes());
+    assert_eq!(&bytes[64..68], &5u32.to_ne_bytes());
+
+    assert_eq!(
+        declaration::<Params>(AddressSpace::Uniform).unwrap(),
+        "struct Inner {
+    s: f32,
+    @size(12) t: f32,
+}
+
+struct Params {
+    position: vec3<f32>,
+    scale: f32,
+    rotation: mat3x3<f32>,
+    count: u32,
+    @align(16) inner: Inner,
+    colors: array<vec4<f32>, 2>,
+}
+"
+    );
+    assert_eq!(
+        declaration::<Params>(AddressSpace::Storage).unwrap(),
+        "struct Inner {
+    s: f32,
+    t: f32,
+}
+
+struct Params {
+    position: vec3<f32>,
+    scale: f32,
+    rotation: mat3x3<f32>,
+    count: u32,
+    inner: Inner,
+    colors: array<vec4<f32>, 2>,
+}
+"
+    );
+
+    #[derive(Clone, Copy, Default, Uniform)]
+    struct Weights {
+        weights: [vec2; 4],
+        enabled: boolean,
+    }
+
+    assert_eq!(
+        declaration::<Weights>(AddressSpace::Uniform),
+        Err(Error::ArrayStride {
+            member: "weights",
+            stride: 16,
+            natural: 8,
+        })
+    );
+    assert_eq!(
+        declaration::<Weights>(AddressSpace::Storage),
+        Err(Error::UnsupportedType {
+            member: "enabled",
+            ty: "bool",
+        })
+    );
+}
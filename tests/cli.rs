@@ -0,0 +1,106 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_glsl-layout"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+fn error(args: &[&str], code: i32, message: &str) {
+    let output = run(args);
+    assert_eq!(output.status.code(), Some(code));
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(message), "{}", stderr);
+}
+
+#[test]
+fn test_tables() {
+    assert_eq!(
+        stdout(&["tests/test_include.glsl"]),
+        "uniform Camera (std140, 240 bytes)
+  path                type     offset size array stride matrix stride padding
+  view                mat4          0   64            -            16       0
+  proj                mat4         64   64            -            16       0
+  position            vec3        128   12            -             -       0
+  orthographic        bool        140    4            -             -       0
+  lights              Light[4]    144   64           16             -       0
+  lights[0].position  vec3        144   12            -             -       0
+  lights[0].intensity float       156    4            -             -       0
+  weights             float[2]    208   32           16             -       0
+
+buffer Particles (std430, 40 bytes)
+  path       type    offset size array stride matrix stride padding
+  count      uint         0    4            -             -       4
+  velocities vec2[4]      8   32            8             -       0
+
+buffer Lights (std430, 32 bytes)
+  path               type    offset size array stride matrix stride padding
+  count              uint         0    4            -             -      12
+  items              Light[]     16    -           16             -       -
+  items[0].position  vec3        16   12            -             -       0
+  items[0].intensity float       28    4            -             -       0
+"
+    );
+}
+
+#[test]
+fn test_json() {
+    let json = stdout(&["--json", "tests/test_include.glsl"]);
+    assert!(json.starts_with(
+        "[
+  {
+    \"kind\": \"uniform\",
+    \"name\": \"Camera\",
+    \"layout\": \"std140\",
+    \"size\": 240,
+    \"runtime_sized\": false,
+    \"members\": [
+      {\"path\": \"view\", \"type\": \"mat4\", \"offset\": 0, \"size\": 64, \"array_stride\": null, \"matrix_stride\": 16, \"padding\": 0},"
+    ));
+    assert!(json.contains(
+        "{\"path\": \"items\", \"type\": \"Light[]\", \"offset\": 16, \"size\": null, \"array_stride\": 16, \"matrix_stride\": null, \"padding\": null}"
+    ));
+    assert!(json.ends_with("    ]\n  }\n]\n"));
+    assert_eq!(json.matches("\"kind\"").count(), 3);
+}
+
+#[test]
+fn test_layout_override() {
+    let json = stdout(&["--layout", "scalar", "--json", "tests/test_include.glsl"]);
+    assert_eq!(json.matches("\"layout\": \"scalar\"").count(), 3);
+    assert!(json.contains("\"size\": 216,"));
+    assert!(json.contains(
+        "{\"path\": \"weights\", \"type\": \"float[2]\", \"offset\": 208, \"size\": 8, \"array_stride\": 4, \"matrix_stride\": null, \"padding\": 0}"
+    ));
+
+    let tables = stdout(&["tests/test_include.glsl", "--layout", "std430"]);
+    assert!(tables.starts_with("uniform Camera (std430, 224 bytes)\n"));
+}
+
+#[test]
+fn test_arguments() {
+    assert!(stdout(&["--help"]).starts_with("Usage: glsl-layout"));
+    error(&[], 2, "error: GLSL file is not specified");
+    error(&["--layout"], 2, "error: `--layout` requires a value");
+    error(
+        &["--layout", "std150", "a.glsl"],
+        2,
+        "error: Unknown layout `std150`",
+    );
+    error(&["--yaml", "a.glsl"], 2, "error: Unknown option `--yaml`");
+    error(
+        &["a.glsl", "b.glsl"],
+        2,
+        "error: Unexpected argument `b.glsl`",
+    );
+    error(&["tests/missing.glsl"], 1, "error: tests/missing.glsl: ");
+}
@@ -0,0 +1,22 @@
+// Declarations used by tests.
+
+#define MAX_LIGHTS 4
+
+struct Light {
+    vec3 position;
+    float intensity;
+};
+
+layout(std140, binding = 0) uniform Camera {
+    mat4 view;
+    mat4 proj;
+    vec3 position;
+    bool orthographic;
+    Light lights[MAX_LIGHTS];
+    float weights[2];
+} camera;
+
+layout(std430, binding = 1) buffer Particles {
+    uint count;
+    vec2 velocities[MAX_LIGHTS];
+};
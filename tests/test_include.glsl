@@ -0,0 +1,6 @@
+#include "test.glsl"
+
+layout(std430) buffer Lights {
+    uint count;
+    Light items[];
+};